@@ -0,0 +1,81 @@
+//! Compares the happy path of `unwrap_none` against a hand-written check,
+//! against `Option::is_some` for a niche-optimized option, and against
+//! `unwrap_none_notrack`.
+
+use std::hint::black_box;
+use std::num::NonZeroU32;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use unwrap_none::UnwrapNone;
+
+fn nones() -> Vec<Option<u64>> {
+    vec![None; 4096]
+}
+
+fn happy_path(c: &mut Criterion) {
+    let options = nones();
+    let mut group = c.benchmark_group("none");
+    group.bench_function("unwrap_none", |b| {
+        b.iter(|| {
+            for &opt in black_box(&options) {
+                opt.unwrap_none();
+            }
+        })
+    });
+    group.bench_function("if_let_some", |b| {
+        b.iter(|| {
+            for &opt in black_box(&options) {
+                if let Some(val) = opt {
+                    panic!(
+                        "called `Option::unwrap_none()` on a `Some` value: {:?}",
+                        val
+                    );
+                }
+            }
+        })
+    });
+    group.finish();
+}
+
+fn niche(c: &mut Criterion) {
+    let options = vec![None::<NonZeroU32>; 4096];
+    let mut group = c.benchmark_group("niche");
+    group.bench_function("unwrap_none", |b| {
+        b.iter(|| {
+            for &opt in black_box(&options) {
+                opt.unwrap_none();
+            }
+        })
+    });
+    group.bench_function("is_some", |b| {
+        b.iter(|| {
+            for &opt in black_box(&options) {
+                black_box(opt.is_some());
+            }
+        })
+    });
+    group.finish();
+}
+
+fn track_caller(c: &mut Criterion) {
+    let options = nones();
+    let mut group = c.benchmark_group("track_caller");
+    group.bench_function("unwrap_none", |b| {
+        b.iter(|| {
+            for &opt in black_box(&options) {
+                opt.unwrap_none();
+            }
+        })
+    });
+    group.bench_function("unwrap_none_notrack", |b| {
+        b.iter(|| {
+            for &opt in black_box(&options) {
+                opt.unwrap_none_notrack();
+            }
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, happy_path, niche, track_caller);
+criterion_main!(benches);
@@ -0,0 +1,42 @@
+//! Calls `expect_none` on 64 distinct types, to check that the panic path is
+//! not monomorphized per type, and exports checks whose codegen CI inspects.
+//!
+//! CI builds this in release mode and asserts that the binary contains a
+//! single `expect_none_failed` symbol, and that the happy path of every
+//! `check_*` function is a compare, a branch and the return, without any
+//! calls.
+
+use std::hint::black_box;
+use std::num::NonZeroU32;
+
+use unwrap_none::UnwrapNone;
+
+#[derive(Debug)]
+struct Slot<const N: usize>;
+
+macro_rules! check {
+    ($($n:literal)+) => {
+        $(black_box(None::<Slot<$n>>).expect_none("slot is occupied");)+
+    };
+}
+
+#[no_mangle]
+pub fn check_u64(opt: Option<u64>) {
+    opt.unwrap_none();
+}
+
+#[no_mangle]
+pub fn check_nonzero_u32(opt: Option<NonZeroU32>) {
+    opt.unwrap_none();
+}
+
+fn main() {
+    check!(
+        0 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16 17 18 19 20 21 22 23 24 25 26 27 28 29 30 31
+        32 33 34 35 36 37 38 39 40 41 42 43 44 45 46 47 48 49 50 51 52 53 54 55 56 57 58 59 60 61
+        62 63
+    );
+    // Called through a pointer, so the checks are kept as separate symbols.
+    black_box(check_u64 as fn(_))(None);
+    black_box(check_nonzero_u32 as fn(_))(None);
+}
@@ -0,0 +1,422 @@
+use core::fmt;
+
+use crate::failed;
+
+const UNWRAP_NONE_MSG: &str = "called `UnwrapNoneAll::unwrap_none()` on a `Some` value";
+const UNWRAP_NONE_ALL_MSG: &str = "called `unwrap_none_all()` on a `Some` value";
+const EXPECT_NONE_LOOP_MSG: &str = "called `expect_none_loop()` on a `Some` value";
+
+/// Expects every [`Option`] in a collection of options to be [`None`].
+///
+/// This is implemented for arrays of options, for tuples of up to 12 options,
+/// which may each contain a different type, and with the `alloc` feature for
+/// [`Vec`]s of options.
+///
+/// [`Vec`]: https://doc.rust-lang.org/alloc/vec/struct.Vec.html
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// use unwrap_none::UnwrapNoneAll;
+///
+/// let mut names = HashMap::new();
+/// let mut ages = HashMap::new();
+/// (names.insert(1, "Alice"), ages.insert(1, 30)).unwrap_none();
+/// ```
+///
+/// ```
+/// use std::panic;
+///
+/// use unwrap_none::UnwrapNoneAll;
+///
+/// # #[cfg(feature = "enforce")] {
+/// let err = panic::catch_unwind(|| (None::<i32>, None::<&str>, Some('c')).unwrap_none())
+///     .unwrap_err();
+/// let msg = err.downcast_ref::<String>().unwrap();
+/// assert!(msg.ends_with("at index 2: 'c'"));
+/// # }
+/// ```
+pub trait UnwrapNoneAll {
+    /// Consumes `self` while expecting every element to be [`None`] and
+    /// returning nothing.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any element is a [`Some`], with a panic message including the
+    /// passed message, the index of the first [`Some`] and its content.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use unwrap_none::UnwrapNoneAll;
+    ///
+    /// [None::<i32>; 4].expect_none("slot is occupied");
+    /// ```
+    ///
+    /// ```should_panic
+    /// use unwrap_none::UnwrapNoneAll;
+    ///
+    /// [None, Some(1), None].expect_none("slot is occupied");
+    /// # #[cfg(not(feature = "enforce"))]
+    /// # panic!("the checks are disabled");
+    /// ```
+    fn expect_none(self, msg: &str);
+
+    /// Consumes `self` while expecting every element to be [`None`] and
+    /// returning nothing.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any element is a [`Some`], with a panic message including the
+    /// index of the first [`Some`] and its content.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use unwrap_none::UnwrapNoneAll;
+    ///
+    /// [None::<i32>; 4].unwrap_none();
+    /// ```
+    ///
+    /// ```
+    /// use std::panic;
+    ///
+    /// use unwrap_none::UnwrapNoneAll;
+    ///
+    /// # #[cfg(feature = "enforce")] {
+    /// let err = panic::catch_unwind(|| [None, Some(1), None].unwrap_none()).unwrap_err();
+    /// let msg = err.downcast_ref::<String>().unwrap();
+    /// assert!(msg.ends_with("at index 1: 1"));
+    /// # }
+    /// ```
+    fn unwrap_none(self);
+
+    /// Same as [`expect_none`](UnwrapNoneAll::expect_none), under a name that
+    /// makes clear at the call site that every element is checked.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any element is a [`Some`], with a panic message including the
+    /// passed message, the index of the first [`Some`] and its content.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use unwrap_none::UnwrapNoneAll;
+    ///
+    /// [None::<i32>; 4].expect_none_all("slot is occupied");
+    /// ```
+    #[inline]
+    #[track_caller]
+    fn expect_none_all(self, msg: &str)
+    where
+        Self: Sized,
+    {
+        self.expect_none(msg);
+    }
+
+    /// Same as [`unwrap_none`](UnwrapNoneAll::unwrap_none), under a name that
+    /// makes clear at the call site that every element is checked.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any element is a [`Some`], with a panic message including the
+    /// index of the first [`Some`] and its content.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::panic;
+    ///
+    /// use unwrap_none::UnwrapNoneAll;
+    ///
+    /// # #[cfg(feature = "enforce")] {
+    /// let err = panic::catch_unwind(|| [None, Some(1), None].unwrap_none_all()).unwrap_err();
+    /// let msg = err.downcast_ref::<String>().unwrap();
+    /// assert_eq!(msg, "called `unwrap_none_all()` on a `Some` value at index 1: 1");
+    /// # }
+    /// ```
+    #[inline]
+    #[track_caller]
+    fn unwrap_none_all(self)
+    where
+        Self: Sized,
+    {
+        self.expect_none(UNWRAP_NONE_ALL_MSG);
+    }
+}
+
+impl<T, const N: usize> UnwrapNoneAll for [Option<T>; N]
+where
+    T: fmt::Debug,
+{
+    #[inline]
+    #[track_caller]
+    fn expect_none(self, msg: &str) {
+        for (index, opt) in self.into_iter().enumerate() {
+            if let Some(val) = failed::enforced(opt) {
+                failed::expect_none_at_failed(msg, index, &val);
+            }
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn unwrap_none(self) {
+        self.expect_none(UNWRAP_NONE_MSG);
+    }
+}
+
+/// Available with the `alloc` feature.
+///
+/// The vector is consumed up to and including the first [`Some`], the
+/// remaining options are dropped along with it.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashMap;
+/// use std::panic;
+///
+/// use unwrap_none::UnwrapNoneAll;
+///
+/// let mut map = HashMap::new();
+/// let inserted: Vec<_> = (0..4).map(|i| map.insert(i, i)).collect();
+/// inserted.expect_none_all("duplicate key");
+///
+/// let inserted: Vec<_> = [4, 5, 2, 3].into_iter().map(|i| map.insert(i, i * 10)).collect();
+/// # #[cfg(feature = "enforce")] {
+/// let err = panic::catch_unwind(|| inserted.expect_none_all("duplicate key")).unwrap_err();
+/// let msg = err.downcast_ref::<String>().unwrap();
+/// assert_eq!(msg, "duplicate key at index 2: 2");
+/// # }
+/// ```
+///
+/// ```
+/// use unwrap_none::UnwrapNoneAll;
+///
+/// vec![None::<u8>; 16].unwrap_none_all();
+/// ```
+#[cfg(feature = "alloc")]
+impl<T> UnwrapNoneAll for alloc::vec::Vec<Option<T>>
+where
+    T: fmt::Debug,
+{
+    #[inline]
+    #[track_caller]
+    fn expect_none(self, msg: &str) {
+        for (index, opt) in self.into_iter().enumerate() {
+            if let Some(val) = failed::enforced(opt) {
+                failed::expect_none_at_failed(msg, index, &val);
+            }
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn unwrap_none(self) {
+        self.expect_none(UNWRAP_NONE_MSG);
+    }
+}
+
+macro_rules! impl_tuple {
+    ($($name:ident $index:tt),+) => {
+        impl<$($name),+> UnwrapNoneAll for ($(Option<$name>,)+)
+        where
+            $($name: fmt::Debug,)+
+        {
+            #[inline]
+            #[track_caller]
+            fn expect_none(self, msg: &str) {
+                $(
+                    if let Some(val) = failed::enforced(self.$index) {
+                        failed::expect_none_at_failed(msg, $index, &val);
+                    }
+                )+
+            }
+
+            #[inline]
+            #[track_caller]
+            fn unwrap_none(self) {
+                self.expect_none(UNWRAP_NONE_MSG);
+            }
+        }
+    };
+}
+
+impl_tuple!(A 0);
+impl_tuple!(A 0, B 1);
+impl_tuple!(A 0, B 1, C 2);
+impl_tuple!(A 0, B 1, C 2, D 3);
+impl_tuple!(A 0, B 1, C 2, D 3, E 4);
+impl_tuple!(A 0, B 1, C 2, D 3, E 4, F 5);
+impl_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+impl_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
+impl_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8);
+impl_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9);
+impl_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10);
+impl_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11);
+
+/// Consumes an iterator of options while expecting every option to be
+/// [`None`].
+///
+/// Iteration stops at the first [`Some`], i.e. any options after it are not
+/// consumed.
+///
+/// # Panics
+///
+/// Panics if any option is a [`Some`], with a panic message including the
+/// index of the first [`Some`] and its content.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// let mut squares = HashMap::new();
+/// unwrap_none::unwrap_none_all((-10..=10).map(|i| squares.insert(i, i * i)));
+/// ```
+///
+/// ```
+/// use std::panic;
+///
+/// let mut consumed = 0;
+/// let options = [None, None, Some(3), Some(4)];
+/// # #[cfg(feature = "enforce")] {
+/// let err = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+///     unwrap_none::unwrap_none_all(options.into_iter().inspect(|_| consumed += 1))
+/// }))
+/// .unwrap_err();
+/// let msg = err.downcast_ref::<String>().unwrap();
+/// assert!(msg.ends_with("at index 2: 3"));
+/// // The last option was not consumed.
+/// assert_eq!(consumed, 3);
+/// # }
+/// ```
+#[inline]
+#[track_caller]
+pub fn unwrap_none_all<I, T>(iter: I)
+where
+    I: IntoIterator<Item = Option<T>>,
+    T: fmt::Debug,
+{
+    for (index, opt) in iter.into_iter().enumerate() {
+        if let Some(val) = failed::enforced(opt) {
+            failed::expect_none_at_failed(UNWRAP_NONE_ALL_MSG, index, &val);
+        }
+    }
+}
+
+/// Repeatedly calls `f` until it returns [`None`], expecting every option it
+/// returns before that to be [`None`].
+///
+/// The outer option signals the end of the stream, the inner option is the
+/// checked value. This is [`unwrap_none_all`] for streams that are produced
+/// by a closure instead of an iterator.
+///
+/// # Panics
+///
+/// Panics if `f` returns `Some(Some(_))`, with a panic message including the
+/// number of preceding calls and the content of the inner [`Some`]. `f` is not
+/// called again afterwards.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// let mut map = HashMap::new();
+/// let mut keys = 0..10;
+/// unwrap_none::expect_none_loop(|| keys.next().map(|k| map.insert(k, ())));
+/// assert_eq!(map.len(), 10);
+/// ```
+///
+/// ```
+/// use std::collections::HashMap;
+/// use std::panic;
+///
+/// # #[cfg(feature = "enforce")] {
+/// let mut map = HashMap::new();
+/// let mut keys = [1, 2, 1, 3].into_iter();
+/// let err = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+///     unwrap_none::expect_none_loop(|| keys.next().map(|k| map.insert(k, k * 10)))
+/// }))
+/// .unwrap_err();
+/// let msg = err.downcast_ref::<String>().unwrap();
+/// assert!(msg.ends_with("at index 2: 10"));
+/// // The last key was not consumed.
+/// assert_eq!(keys.next(), Some(3));
+/// # }
+/// ```
+#[inline]
+#[track_caller]
+pub fn expect_none_loop<T, F>(mut f: F)
+where
+    F: FnMut() -> Option<Option<T>>,
+    T: fmt::Debug,
+{
+    let mut index = 0;
+    while let Some(opt) = f() {
+        if let Some(val) = failed::enforced(opt) {
+            failed::expect_none_at_failed(EXPECT_NONE_LOOP_MSG, index, &val);
+        }
+        index += 1;
+    }
+}
+
+/// Calls `f` until it returns [`None`], at most `max_tries` times.
+///
+/// This is useful for resources that are expected to become free eventually,
+/// e.g. shared state in tests. `f` is always called at least once, even if
+/// `max_tries` is `0`.
+///
+/// # Panics
+///
+/// Panics if `f` still returns a [`Some`] after `max_tries` calls, with a panic
+/// message including the passed message, the number of calls, and the content
+/// of the last [`Some`].
+///
+/// # Examples
+///
+/// ```
+/// let mut queue = vec![1, 2, 3];
+/// unwrap_none::expect_none_within(|| queue.pop(), 4, "queue is not drained");
+/// assert!(queue.is_empty());
+/// ```
+///
+/// ```
+/// use std::panic;
+///
+/// let mut queue = vec![1, 2, 3, 4];
+/// # #[cfg(feature = "enforce")] {
+/// let err = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+///     unwrap_none::expect_none_within(|| queue.pop(), 3, "queue is not drained")
+/// }))
+/// .unwrap_err();
+/// let msg = err.downcast_ref::<String>().unwrap();
+/// assert_eq!(msg, "queue is not drained after 3 tries: 2");
+/// assert_eq!(queue, [1]);
+/// # }
+/// ```
+#[inline]
+#[track_caller]
+pub fn expect_none_within<T, F>(mut f: F, max_tries: usize, msg: &str)
+where
+    F: FnMut() -> Option<T>,
+    T: fmt::Debug,
+{
+    let max_tries = max_tries.max(1);
+    for tries in 1..=max_tries {
+        let Some(val) = f() else {
+            return;
+        };
+        // Only the last value is unexpected, the previous ones are retried.
+        if tries == max_tries {
+            if let Some(val) = failed::enforced(Some(val)) {
+                failed::expect_none_within_failed(msg, tries, &val);
+            }
+        }
+    }
+}
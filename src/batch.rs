@@ -0,0 +1,220 @@
+use core::fmt;
+#[cfg(not(feature = "alloc"))]
+use core::fmt::Write;
+
+use crate::failed::{self, panic_in_context};
+
+/// The capacity of the violation report without the `alloc` feature, in bytes.
+#[cfg(not(feature = "alloc"))]
+const REPORT_CAPACITY: usize = 512;
+
+/// Checks many options for [`None`] and reports every unexpected [`Some`] at
+/// once.
+///
+/// This is useful for validation passes, where seeing every problem at once
+/// beats fixing them one by one. With the `alloc` feature every violation is
+/// kept. Without it, they are formatted into a fixed buffer of 512 bytes and
+/// the report is cut off once it is full, but all violations are still
+/// counted.
+///
+/// # Examples
+///
+/// ```
+/// use std::panic;
+///
+/// use unwrap_none::NoneBatch;
+///
+/// let mut batch = NoneBatch::new();
+/// batch.check(None::<u32>, "timeout").check(None::<&str>, "proxy");
+/// batch.finish();
+///
+/// # #[cfg(feature = "enforce")] {
+/// let err = panic::catch_unwind(|| {
+///     let mut batch = NoneBatch::new();
+///     batch
+///         .check(Some(30), "timeout")
+///         .check(None::<&str>, "proxy")
+///         .check(Some("eu-west"), "region");
+///     batch.finish();
+/// })
+/// .unwrap_err();
+/// assert_eq!(
+///     err.downcast_ref::<String>().unwrap(),
+///     "2 unexpected `Some` values:\n  timeout: 30\n  region: \"eu-west\"",
+/// );
+/// # }
+/// ```
+///
+/// Without the `alloc` feature, the report ends with the last violation that
+/// was cut off:
+///
+/// ```
+/// use std::panic;
+///
+/// use unwrap_none::NoneBatch;
+///
+/// # #[cfg(all(feature = "enforce", not(feature = "alloc")))] {
+/// let err = panic::catch_unwind(|| {
+///     let mut batch = NoneBatch::new();
+///     for _ in 0..100 {
+///         batch.check(Some("straße"), "größe");
+///     }
+///     batch.finish();
+/// })
+/// .unwrap_err();
+/// let msg = err.downcast_ref::<String>().unwrap();
+/// let report = msg.strip_suffix("\n  ...").unwrap();
+/// let mut lines = report.split('\n');
+/// assert_eq!(lines.next(), Some("100 unexpected `Some` values:"));
+/// // Only the last violation is incomplete.
+/// let last = lines.next_back().unwrap();
+/// assert!("  größe: \"straße\"".starts_with(last));
+/// assert!(lines.all(|line| line == "  größe: \"straße\""));
+/// # }
+/// ```
+#[must_use = "violations are only reported by `finish`"]
+pub struct NoneBatch {
+    count: usize,
+    #[cfg(feature = "alloc")]
+    violations: alloc::vec::Vec<alloc::string::String>,
+    #[cfg(not(feature = "alloc"))]
+    violations: Report,
+}
+
+impl NoneBatch {
+    /// Creates a new batch without any violations.
+    pub const fn new() -> Self {
+        Self {
+            count: 0,
+            #[cfg(feature = "alloc")]
+            violations: alloc::vec::Vec::new(),
+            #[cfg(not(feature = "alloc"))]
+            violations: Report {
+                buf: [0; REPORT_CAPACITY],
+                len: 0,
+                truncated: false,
+            },
+        }
+    }
+
+    /// Checks `opt`, remembering its content along with `label` if it is a
+    /// [`Some`].
+    #[inline]
+    pub fn check<T>(&mut self, opt: Option<T>, label: &str) -> &mut Self
+    where
+        T: fmt::Debug,
+    {
+        if let Some(val) = failed::enforced(opt) {
+            self.record(label, &val);
+        }
+        self
+    }
+
+    /// Returns the number of violations found so far.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Consumes `self` while expecting every checked option to have been
+    /// [`None`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if any checked option was a [`Some`], with a panic message
+    /// including the number of violations, and the label and content of each.
+    #[inline]
+    #[track_caller]
+    pub fn finish(self) {
+        if self.count > 0 {
+            self.failed();
+        }
+    }
+
+    #[inline(never)]
+    #[cold]
+    fn record(&mut self, label: &str, value: &dyn fmt::Debug) {
+        #[cfg(feature = "std")]
+        crate::hook::on_unexpected_some(value);
+        self.count += 1;
+        #[cfg(feature = "alloc")]
+        self.violations
+            .push(alloc::format!("{}: {:?}", label, value));
+        #[cfg(not(feature = "alloc"))]
+        let _ = write!(self.violations, "\n  {}: {:?}", label, value);
+    }
+
+    #[inline(never)]
+    #[cold]
+    #[track_caller]
+    fn failed(&self) -> ! {
+        panic_in_context!(
+            "{} unexpected `Some` values:{}",
+            self.count,
+            Violations(self)
+        )
+    }
+}
+
+impl Default for NoneBatch {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+struct Violations<'a>(&'a NoneBatch);
+
+impl fmt::Display for Violations<'_> {
+    #[cfg(feature = "alloc")]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for violation in &self.0.violations {
+            write!(f, "\n  {}", violation)?;
+        }
+        Ok(())
+    }
+
+    #[cfg(not(feature = "alloc"))]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let report = &self.0.violations;
+        // Only whole characters are written, so this never fails.
+        f.write_str(core::str::from_utf8(&report.buf[..report.len]).unwrap_or_default())?;
+        if report.truncated {
+            f.write_str("\n  ...")?;
+        }
+        Ok(())
+    }
+}
+
+/// A fixed-capacity text buffer, which cuts off anything that does not fit.
+#[cfg(not(feature = "alloc"))]
+struct Report {
+    buf: [u8; REPORT_CAPACITY],
+    len: usize,
+    truncated: bool,
+}
+
+#[cfg(not(feature = "alloc"))]
+impl Write for Report {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        if self.truncated {
+            // Don't append fragments of later violations after the cut.
+            return Err(fmt::Error);
+        }
+        let remaining = REPORT_CAPACITY - self.len;
+        let mut end = s.len();
+        if end > remaining {
+            end = remaining;
+            while !s.is_char_boundary(end) {
+                end -= 1;
+            }
+            self.truncated = true;
+        }
+        self.buf[self.len..self.len + end].copy_from_slice(&s.as_bytes()[..end]);
+        self.len += end;
+        if self.truncated {
+            // Stop formatting the rest of the violation, there is no room for it.
+            Err(fmt::Error)
+        } else {
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,769 @@
+use core::fmt::{self, Write};
+use std::string::{String, ToString};
+
+use serde::ser::{self, Impossible, Serialize};
+
+use crate::failed;
+
+/// Returns the path of the first [`Some`] in the serialized form of `value`,
+/// or [`None`] if every option in it serializes as absent.
+///
+/// This walks `value` through the `serde` data model, so unlike the
+/// `ExpectNoneFields` derive it works for any type
+/// implementing [`Serialize`], including nested structs, collections and types
+/// from other crates. Since it has to serialize the whole value, it is a lot
+/// slower than checking the fields directly.
+///
+/// The path joins struct fields, tuple fields and enum variants with `.`, and
+/// puts sequence indices and map keys in brackets, e.g. `servers[1].proxy`.
+/// It is empty if `value` itself is a [`Some`]. Map keys that are not strings,
+/// integers, characters or booleans are written as `?`.
+///
+/// A value that fails to serialize is only checked up to the point where it
+/// failed, use [`expect_blank`] to treat that as a failure instead.
+///
+/// Available with the `serde` feature.
+///
+/// # Examples
+///
+/// ```
+/// use serde::Serialize;
+///
+/// #[derive(Serialize, Default)]
+/// struct Proxy {
+///     host: Option<String>,
+///     port: Option<u16>,
+/// }
+///
+/// #[derive(Serialize, Default)]
+/// struct Config {
+///     timeout: Option<u32>,
+///     proxies: Vec<Proxy>,
+/// }
+///
+/// let mut config = Config {
+///     proxies: vec![Proxy::default(), Proxy::default()],
+///     ..Config::default()
+/// };
+/// assert_eq!(unwrap_none::first_some_path(&config), None);
+///
+/// config.proxies[1].port = Some(8080);
+/// assert_eq!(
+///     unwrap_none::first_some_path(&config).as_deref(),
+///     Some("proxies[1].port"),
+/// );
+/// ```
+pub fn first_some_path<T>(value: &T) -> Option<String>
+where
+    T: Serialize + ?Sized,
+{
+    match walk(value) {
+        Err(Stop::Found(path)) => Some(path),
+        _ => None,
+    }
+}
+
+/// Expects every option in the serialized form of `value` to be absent.
+///
+/// See [`first_some_path`] for how `value` is checked.
+///
+/// Available with the `serde` feature.
+///
+/// # Panics
+///
+/// Panics if any option in `value` serializes as present, with a panic message
+/// including the passed message and the path of the first such option.
+///
+/// Also panics if `value` fails to serialize, as it cannot be fully checked
+/// then, with a panic message including the passed message, the path at which
+/// it failed and the error. This is the case even with
+/// [`PanicMode::LogOnly`](crate::PanicMode::LogOnly), which only logs a
+/// present option instead.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::BTreeMap;
+/// use std::panic;
+///
+/// let mut overrides = BTreeMap::new();
+/// overrides.insert("eu-west", None);
+/// overrides.insert("us-east", None);
+/// unwrap_none::expect_blank(&overrides, "overrides must be blank");
+///
+/// overrides.insert("us-east", Some(3));
+/// # #[cfg(feature = "enforce")] {
+/// let err = panic::catch_unwind(|| {
+///     unwrap_none::expect_blank(&overrides, "overrides must be blank")
+/// })
+/// .unwrap_err();
+/// assert_eq!(
+///     err.downcast_ref::<String>().unwrap(),
+///     "overrides must be blank: `[us-east]` is Some",
+/// );
+/// # }
+/// ```
+///
+/// ```
+/// use std::panic;
+///
+/// use serde::ser::{Error, Serialize, Serializer};
+///
+/// struct Broken;
+///
+/// impl Serialize for Broken {
+///     fn serialize<S: Serializer>(&self, _: S) -> Result<S::Ok, S::Error> {
+///         Err(S::Error::custom("not serializable"))
+///     }
+/// }
+///
+/// #[derive(serde::Serialize)]
+/// struct Config {
+///     inner: Broken,
+///     timeout: Option<u32>,
+/// }
+///
+/// let config = Config { inner: Broken, timeout: None };
+/// # #[cfg(feature = "enforce")] {
+/// let err = panic::catch_unwind(|| unwrap_none::expect_blank(&config, "config must be blank"))
+///     .unwrap_err();
+/// assert_eq!(
+///     err.downcast_ref::<String>().unwrap(),
+///     "config must be blank: failed to serialize at `inner`: not serializable",
+/// );
+/// # }
+///
+/// // A present option is only logged with `PanicMode::LogOnly`.
+/// unwrap_none::set_panic_mode(unwrap_none::PanicMode::LogOnly);
+/// unwrap_none::expect_blank(&Some(3), "config must be blank");
+/// ```
+#[track_caller]
+pub fn expect_blank<T>(value: &T, msg: &str)
+where
+    T: Serialize + ?Sized,
+{
+    if failed::ENFORCE {
+        match walk(value) {
+            Ok(()) => {}
+            Err(Stop::Found(path)) => {
+                if !crate::mode::log_only(core::any::type_name::<T>()) {
+                    failed::expect_blank_failed(msg, &path)
+                }
+            }
+            Err(Stop::Failed { path, error }) => {
+                failed::expect_blank_unserializable(msg, &path.unwrap_or_default(), &error)
+            }
+        }
+    }
+}
+
+fn walk<T>(value: &T) -> Result<(), Stop>
+where
+    T: Serialize + ?Sized,
+{
+    let mut walker = Walker {
+        path: String::new(),
+    };
+    value.serialize(&mut walker).map_err(|stop| stop.at(""))
+}
+
+/// Stops the walk at the first [`Some`], or at an error of the value.
+#[derive(Debug)]
+enum Stop {
+    /// A [`Some`] was found at this path.
+    Found(String),
+    /// Serializing failed, at this path once it is known.
+    Failed { path: Option<String>, error: String },
+}
+
+impl Stop {
+    /// Sets the path of an error, unless a nested value already did.
+    fn at(self, path: &str) -> Self {
+        match self {
+            Stop::Failed { path: None, error } => Stop::Failed {
+                path: Some(path.into()),
+                error,
+            },
+            stop => stop,
+        }
+    }
+
+    fn unsupported_key() -> Self {
+        Stop::Failed {
+            path: None,
+            error: "unsupported map key".into(),
+        }
+    }
+}
+
+impl fmt::Display for Stop {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Stop::Found(path) => write!(f, "found Some at `{}`", path),
+            Stop::Failed { path, error } => {
+                write!(
+                    f,
+                    "failed to serialize at `{}`: {}",
+                    path.as_deref().unwrap_or(""),
+                    error
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for Stop {}
+
+impl ser::Error for Stop {
+    fn custom<M>(msg: M) -> Self
+    where
+        M: fmt::Display,
+    {
+        Stop::Failed {
+            path: None,
+            error: msg.to_string(),
+        }
+    }
+}
+
+struct Walker {
+    path: String,
+}
+
+impl Walker {
+    fn nested<T>(&mut self, segment: fmt::Arguments<'_>, value: &T) -> Result<(), Stop>
+    where
+        T: Serialize + ?Sized,
+    {
+        let len = self.path.len();
+        self.push(segment);
+        let result = value.serialize(&mut *self);
+        result.map_err(|stop| stop.at(&self.path))?;
+        self.path.truncate(len);
+        Ok(())
+    }
+
+    fn push(&mut self, segment: fmt::Arguments<'_>) {
+        let _ = self.path.write_fmt(segment);
+    }
+
+    fn compound(&mut self, variant: Option<&str>) -> Compound<'_> {
+        let restore = self.path.len();
+        if let Some(variant) = variant {
+            self.push(format_args!("{}", Field(restore, variant)));
+        }
+        Compound {
+            walker: self,
+            index: 0,
+            key: String::new(),
+            restore,
+        }
+    }
+}
+
+/// Formats a named segment, with a leading `.` unless it starts the path.
+struct Field<'a>(usize, &'a str);
+
+impl fmt::Display for Field<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0 > 0 {
+            f.write_str(".")?;
+        }
+        f.write_str(self.1)
+    }
+}
+
+impl<'a> ser::Serializer for &'a mut Walker {
+    type Ok = ();
+    type Error = Stop;
+    type SerializeSeq = Compound<'a>;
+    type SerializeTuple = Compound<'a>;
+    type SerializeTupleStruct = Compound<'a>;
+    type SerializeTupleVariant = Compound<'a>;
+    type SerializeMap = Compound<'a>;
+    type SerializeStruct = Compound<'a>;
+    type SerializeStructVariant = Compound<'a>;
+
+    fn serialize_bool(self, _: bool) -> Result<(), Stop> {
+        Ok(())
+    }
+
+    fn serialize_i8(self, _: i8) -> Result<(), Stop> {
+        Ok(())
+    }
+
+    fn serialize_i16(self, _: i16) -> Result<(), Stop> {
+        Ok(())
+    }
+
+    fn serialize_i32(self, _: i32) -> Result<(), Stop> {
+        Ok(())
+    }
+
+    fn serialize_i64(self, _: i64) -> Result<(), Stop> {
+        Ok(())
+    }
+
+    fn serialize_i128(self, _: i128) -> Result<(), Stop> {
+        Ok(())
+    }
+
+    fn serialize_u8(self, _: u8) -> Result<(), Stop> {
+        Ok(())
+    }
+
+    fn serialize_u16(self, _: u16) -> Result<(), Stop> {
+        Ok(())
+    }
+
+    fn serialize_u32(self, _: u32) -> Result<(), Stop> {
+        Ok(())
+    }
+
+    fn serialize_u64(self, _: u64) -> Result<(), Stop> {
+        Ok(())
+    }
+
+    fn serialize_u128(self, _: u128) -> Result<(), Stop> {
+        Ok(())
+    }
+
+    fn serialize_f32(self, _: f32) -> Result<(), Stop> {
+        Ok(())
+    }
+
+    fn serialize_f64(self, _: f64) -> Result<(), Stop> {
+        Ok(())
+    }
+
+    fn serialize_char(self, _: char) -> Result<(), Stop> {
+        Ok(())
+    }
+
+    fn serialize_str(self, _: &str) -> Result<(), Stop> {
+        Ok(())
+    }
+
+    fn serialize_bytes(self, _: &[u8]) -> Result<(), Stop> {
+        Ok(())
+    }
+
+    fn serialize_none(self) -> Result<(), Stop> {
+        Ok(())
+    }
+
+    fn serialize_some<T>(self, _: &T) -> Result<(), Stop>
+    where
+        T: Serialize + ?Sized,
+    {
+        Err(Stop::Found(self.path.clone()))
+    }
+
+    fn serialize_unit(self) -> Result<(), Stop> {
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, _: &'static str) -> Result<(), Stop> {
+        Ok(())
+    }
+
+    fn serialize_unit_variant(self, _: &'static str, _: u32, _: &'static str) -> Result<(), Stop> {
+        Ok(())
+    }
+
+    fn serialize_newtype_struct<T>(self, _: &'static str, value: &T) -> Result<(), Stop>
+    where
+        T: Serialize + ?Sized,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<(), Stop>
+    where
+        T: Serialize + ?Sized,
+    {
+        let len = self.path.len();
+        self.nested(format_args!("{}", Field(len, variant)), value)
+    }
+
+    fn serialize_seq(self, _: Option<usize>) -> Result<Compound<'a>, Stop> {
+        Ok(self.compound(None))
+    }
+
+    fn serialize_tuple(self, _: usize) -> Result<Compound<'a>, Stop> {
+        Ok(self.compound(None))
+    }
+
+    fn serialize_tuple_struct(self, _: &'static str, _: usize) -> Result<Compound<'a>, Stop> {
+        Ok(self.compound(None))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+        _: usize,
+    ) -> Result<Compound<'a>, Stop> {
+        Ok(self.compound(Some(variant)))
+    }
+
+    fn serialize_map(self, _: Option<usize>) -> Result<Compound<'a>, Stop> {
+        Ok(self.compound(None))
+    }
+
+    fn serialize_struct(self, _: &'static str, _: usize) -> Result<Compound<'a>, Stop> {
+        Ok(self.compound(None))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+        _: usize,
+    ) -> Result<Compound<'a>, Stop> {
+        Ok(self.compound(Some(variant)))
+    }
+}
+
+struct Compound<'a> {
+    walker: &'a mut Walker,
+    index: usize,
+    key: String,
+    restore: usize,
+}
+
+impl Compound<'_> {
+    fn element<T>(&mut self, value: &T) -> Result<(), Stop>
+    where
+        T: Serialize + ?Sized,
+    {
+        let index = self.index;
+        self.index += 1;
+        self.walker.nested(format_args!("[{}]", index), value)
+    }
+
+    fn field<T>(&mut self, value: &T) -> Result<(), Stop>
+    where
+        T: Serialize + ?Sized,
+    {
+        let index = self.index;
+        self.index += 1;
+        let len = self.walker.path.len();
+        self.walker
+            .nested(format_args!("{}", Field(len, &index.to_string())), value)
+    }
+
+    fn named<T>(&mut self, key: &str, value: &T) -> Result<(), Stop>
+    where
+        T: Serialize + ?Sized,
+    {
+        let len = self.walker.path.len();
+        self.walker
+            .nested(format_args!("{}", Field(len, key)), value)
+    }
+
+    fn end(self) -> Result<(), Stop> {
+        self.walker.path.truncate(self.restore);
+        Ok(())
+    }
+}
+
+impl ser::SerializeSeq for Compound<'_> {
+    type Ok = ();
+    type Error = Stop;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), Stop>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), Stop> {
+        Compound::end(self)
+    }
+}
+
+impl ser::SerializeTuple for Compound<'_> {
+    type Ok = ();
+    type Error = Stop;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), Stop>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.field(value)
+    }
+
+    fn end(self) -> Result<(), Stop> {
+        Compound::end(self)
+    }
+}
+
+impl ser::SerializeTupleStruct for Compound<'_> {
+    type Ok = ();
+    type Error = Stop;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), Stop>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.field(value)
+    }
+
+    fn end(self) -> Result<(), Stop> {
+        Compound::end(self)
+    }
+}
+
+impl ser::SerializeTupleVariant for Compound<'_> {
+    type Ok = ();
+    type Error = Stop;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), Stop>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.field(value)
+    }
+
+    fn end(self) -> Result<(), Stop> {
+        Compound::end(self)
+    }
+}
+
+impl ser::SerializeMap for Compound<'_> {
+    type Ok = ();
+    type Error = Stop;
+
+    fn serialize_key<T>(&mut self, key: &T) -> Result<(), Stop>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.key = key.serialize(KeyName).unwrap_or_else(|_| "?".into());
+        Ok(())
+    }
+
+    fn serialize_value<T>(&mut self, value: &T) -> Result<(), Stop>
+    where
+        T: Serialize + ?Sized,
+    {
+        let key = core::mem::take(&mut self.key);
+        self.walker.nested(format_args!("[{}]", key), value)
+    }
+
+    fn end(self) -> Result<(), Stop> {
+        Compound::end(self)
+    }
+}
+
+impl ser::SerializeStruct for Compound<'_> {
+    type Ok = ();
+    type Error = Stop;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), Stop>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.named(key, value)
+    }
+
+    fn end(self) -> Result<(), Stop> {
+        Compound::end(self)
+    }
+}
+
+impl ser::SerializeStructVariant for Compound<'_> {
+    type Ok = ();
+    type Error = Stop;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), Stop>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.named(key, value)
+    }
+
+    fn end(self) -> Result<(), Stop> {
+        Compound::end(self)
+    }
+}
+
+/// Formats simple map keys for the path, failing for anything else.
+struct KeyName;
+
+impl ser::Serializer for KeyName {
+    type Ok = String;
+    type Error = Stop;
+    type SerializeSeq = Impossible<String, Stop>;
+    type SerializeTuple = Impossible<String, Stop>;
+    type SerializeTupleStruct = Impossible<String, Stop>;
+    type SerializeTupleVariant = Impossible<String, Stop>;
+    type SerializeMap = Impossible<String, Stop>;
+    type SerializeStruct = Impossible<String, Stop>;
+    type SerializeStructVariant = Impossible<String, Stop>;
+
+    fn serialize_bool(self, v: bool) -> Result<String, Stop> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<String, Stop> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<String, Stop> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<String, Stop> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<String, Stop> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<String, Stop> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<String, Stop> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<String, Stop> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<String, Stop> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<String, Stop> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<String, Stop> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_f32(self, _: f32) -> Result<String, Stop> {
+        Err(Stop::unsupported_key())
+    }
+
+    fn serialize_f64(self, _: f64) -> Result<String, Stop> {
+        Err(Stop::unsupported_key())
+    }
+
+    fn serialize_char(self, v: char) -> Result<String, Stop> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_str(self, v: &str) -> Result<String, Stop> {
+        Ok(v.into())
+    }
+
+    fn serialize_bytes(self, _: &[u8]) -> Result<String, Stop> {
+        Err(Stop::unsupported_key())
+    }
+
+    fn serialize_none(self) -> Result<String, Stop> {
+        Err(Stop::unsupported_key())
+    }
+
+    fn serialize_some<T>(self, _: &T) -> Result<String, Stop>
+    where
+        T: Serialize + ?Sized,
+    {
+        Err(Stop::unsupported_key())
+    }
+
+    fn serialize_unit(self) -> Result<String, Stop> {
+        Err(Stop::unsupported_key())
+    }
+
+    fn serialize_unit_struct(self, _: &'static str) -> Result<String, Stop> {
+        Err(Stop::unsupported_key())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+    ) -> Result<String, Stop> {
+        Ok(variant.into())
+    }
+
+    fn serialize_newtype_struct<T>(self, _: &'static str, value: &T) -> Result<String, Stop>
+    where
+        T: Serialize + ?Sized,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: &T,
+    ) -> Result<String, Stop>
+    where
+        T: Serialize + ?Sized,
+    {
+        Err(Stop::unsupported_key())
+    }
+
+    fn serialize_seq(self, _: Option<usize>) -> Result<Self::SerializeSeq, Stop> {
+        Err(Stop::unsupported_key())
+    }
+
+    fn serialize_tuple(self, _: usize) -> Result<Self::SerializeTuple, Stop> {
+        Err(Stop::unsupported_key())
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleStruct, Stop> {
+        Err(Stop::unsupported_key())
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleVariant, Stop> {
+        Err(Stop::unsupported_key())
+    }
+
+    fn serialize_map(self, _: Option<usize>) -> Result<Self::SerializeMap, Stop> {
+        Err(Stop::unsupported_key())
+    }
+
+    fn serialize_struct(self, _: &'static str, _: usize) -> Result<Self::SerializeStruct, Stop> {
+        Err(Stop::unsupported_key())
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeStructVariant, Stop> {
+        Err(Stop::unsupported_key())
+    }
+}
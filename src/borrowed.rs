@@ -0,0 +1,114 @@
+use crate::{MaybeDebug, UnwrapNone};
+
+/// Expects a borrowed [`Option`] to be [`None`].
+///
+/// This is implemented for `&Option<T>` and `&mut Option<T>`, as returned by
+/// e.g. struct field accessors, so they can be checked without
+/// [`Option::as_ref`] or a clone. Options containing a borrow, such as
+/// `Option<&T>`, are supported by [`UnwrapNone`](crate::UnwrapNone) instead.
+///
+/// # Examples
+///
+/// ```
+/// use unwrap_none::UnwrapNoneRef;
+///
+/// struct Connection {
+///     pending: Option<u32>,
+/// }
+///
+/// impl Connection {
+///     fn pending(&self) -> &Option<u32> {
+///         &self.pending
+///     }
+/// }
+///
+/// let mut conn = Connection { pending: None };
+/// conn.pending().unwrap_none();
+/// (&mut conn.pending).expect_none("request is pending");
+/// // The option is still usable afterwards.
+/// conn.pending = Some(1);
+/// ```
+pub trait UnwrapNoneRef {
+    /// Expects [`None`] without consuming the borrowed option.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is a [`Some`], with a panic message including the
+    /// passed message, and the content of the [`Some`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::panic;
+    ///
+    /// use unwrap_none::UnwrapNoneRef;
+    ///
+    /// struct Slot {
+    ///     value: Option<String>,
+    /// }
+    ///
+    /// let slot = Slot { value: Some("in use".to_string()) };
+    /// # #[cfg(feature = "enforce")] {
+    /// let err = panic::catch_unwind(|| (&slot.value).expect_none("slot is in use")).unwrap_err();
+    /// let msg = err.downcast_ref::<String>().unwrap();
+    /// # #[cfg(not(feature = "minimal-panic"))]
+    /// assert_eq!(msg, "slot is in use: \"in use\"");
+    /// assert_eq!(slot.value.as_deref(), Some("in use"));
+    /// # }
+    /// ```
+    fn expect_none(self, msg: &str);
+
+    /// Expects [`None`] without consuming the borrowed option.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is a [`Some`], with a panic message including the
+    /// content of the [`Some`].
+    ///
+    /// # Examples
+    ///
+    /// ```should_panic
+    /// use unwrap_none::UnwrapNoneRef;
+    ///
+    /// let slot = Some(1);
+    /// let slot = &slot;
+    /// slot.unwrap_none();
+    /// # #[cfg(not(feature = "enforce"))]
+    /// # panic!("the checks are disabled");
+    /// ```
+    fn unwrap_none(self);
+}
+
+impl<T> UnwrapNoneRef for &Option<T>
+where
+    T: MaybeDebug,
+{
+    #[inline]
+    #[track_caller]
+    fn expect_none(self, msg: &str) {
+        UnwrapNone::expect_none(self.as_ref(), msg);
+    }
+
+    #[inline]
+    #[track_caller]
+    fn unwrap_none(self) {
+        UnwrapNone::unwrap_none(self.as_ref());
+    }
+}
+
+impl<T> UnwrapNoneRef for &mut Option<T>
+where
+    T: MaybeDebug,
+{
+    #[inline]
+    #[track_caller]
+    fn expect_none(self, msg: &str) {
+        (&*self).expect_none(msg);
+    }
+
+    #[inline]
+    #[track_caller]
+    fn unwrap_none(self) {
+        (&*self).unwrap_none();
+    }
+}
@@ -0,0 +1,61 @@
+use alloc::boxed::Box;
+
+use crate::failed;
+
+/// Expects an `Option<Box<T>>` to be [`None`] without formatting the boxed
+/// value.
+///
+/// Available with the `alloc` feature.
+pub trait UnwrapNoneBoxed {
+    /// Consumes `self` while expecting [`None`] and returning nothing.
+    ///
+    /// Unlike [`unwrap_none`](crate::UnwrapNone::unwrap_none) this does not
+    /// require `T` to implement [`Debug`](core::fmt::Debug) and never formats
+    /// the boxed value, which may be large.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is a [`Some`], with a panic message including the
+    /// type name of the boxed value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fmt;
+    /// use std::panic;
+    ///
+    /// use unwrap_none::UnwrapNoneBoxed;
+    ///
+    /// struct Frame([u8; 4096]);
+    ///
+    /// impl fmt::Debug for Frame {
+    ///     fn fmt(&self, _: &mut fmt::Formatter<'_>) -> fmt::Result {
+    ///         unreachable!("too expensive to format")
+    ///     }
+    /// }
+    ///
+    /// let pending: Option<Box<Frame>> = None;
+    /// pending.unwrap_none_boxed();
+    ///
+    /// let pending = Some(Box::new(Frame([0; 4096])));
+    /// # #[cfg(feature = "enforce")] {
+    /// let err = panic::catch_unwind(|| pending.unwrap_none_boxed()).unwrap_err();
+    /// let msg = err.downcast_ref::<String>().unwrap();
+    /// assert!(msg.ends_with("::Frame>"));
+    /// # }
+    /// ```
+    fn unwrap_none_boxed(self);
+}
+
+impl<T> UnwrapNoneBoxed for Option<Box<T>>
+where
+    T: ?Sized,
+{
+    #[inline]
+    #[track_caller]
+    fn unwrap_none_boxed(self) {
+        if failed::enforced(self).is_some() {
+            failed::unwrap_none_failed_boxed(core::any::type_name::<T>());
+        }
+    }
+}
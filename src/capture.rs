@@ -0,0 +1,11 @@
+use core::fmt;
+use core::panic::Location;
+
+#[inline(never)]
+#[cold]
+pub(crate) fn capture_unexpected(value: &dyn fmt::Debug, location: &Location<'_>) {
+    sentry_core::capture_message(
+        &std::format!("expected None, got Some({:?}) at {}", value, location),
+        sentry_core::Level::Error,
+    );
+}
@@ -0,0 +1,98 @@
+use core::cell::{Cell, RefCell};
+use core::fmt;
+
+use crate::failed;
+
+/// Expects the option in `cell` to be [`None`], leaving it untouched.
+///
+/// # Panics
+///
+/// Panics if the option is a [`Some`], with a panic message including the
+/// passed message, and the content of the [`Some`].
+///
+/// # Examples
+///
+/// ```
+/// use std::cell::Cell;
+/// use std::panic::{self, AssertUnwindSafe};
+///
+/// let pending = Cell::new(None::<u32>);
+/// unwrap_none::expect_cell_none(&pending, "request still pending");
+/// assert_eq!(pending.get(), None);
+///
+/// pending.set(Some(7));
+/// # #[cfg(feature = "enforce")] {
+/// let err = panic::catch_unwind(AssertUnwindSafe(|| {
+///     unwrap_none::expect_cell_none(&pending, "request still pending")
+/// }))
+/// .unwrap_err();
+/// assert_eq!(err.downcast_ref::<String>().unwrap(), "request still pending: 7");
+/// assert_eq!(pending.get(), Some(7));
+/// # }
+/// ```
+#[inline]
+#[track_caller]
+pub fn expect_cell_none<T>(cell: &Cell<Option<T>>, msg: &str)
+where
+    T: fmt::Debug + Copy,
+{
+    if let Some(val) = failed::enforced(cell.get()) {
+        failed::expect_none_failed(&msg, &val);
+    }
+}
+
+/// Expects the option in `cell` to be [`None`], leaving it untouched.
+///
+/// The option is only borrowed for the duration of the check.
+///
+/// # Panics
+///
+/// Panics if the option is a [`Some`], with a panic message including the
+/// passed message, and the content of the [`Some`].
+///
+/// Also panics if `cell` is currently mutably borrowed, as the option cannot
+/// be checked then. The panic message includes the passed message and says
+/// so.
+///
+/// # Examples
+///
+/// ```
+/// use std::cell::RefCell;
+/// use std::panic::{self, AssertUnwindSafe};
+///
+/// let queue = RefCell::new(None::<Vec<u8>>);
+/// unwrap_none::expect_refcell_none(&queue, "queue not drained");
+/// assert_eq!(*queue.borrow(), None);
+///
+/// *queue.borrow_mut() = Some(vec![1, 2]);
+/// let check = AssertUnwindSafe(|| unwrap_none::expect_refcell_none(&queue, "queue not drained"));
+/// # #[cfg(feature = "enforce")] {
+/// let err = panic::catch_unwind(check).unwrap_err();
+/// assert_eq!(err.downcast_ref::<String>().unwrap(), "queue not drained: [1, 2]");
+/// assert_eq!(*queue.borrow(), Some(vec![1, 2]));
+///
+/// let _writer = queue.borrow_mut();
+/// let check = AssertUnwindSafe(|| unwrap_none::expect_refcell_none(&queue, "queue not drained"));
+/// let err = panic::catch_unwind(check).unwrap_err();
+/// assert_eq!(
+///     err.downcast_ref::<String>().unwrap(),
+///     "queue not drained: cannot check the option, it is mutably borrowed",
+/// );
+/// # }
+/// ```
+#[inline]
+#[track_caller]
+pub fn expect_refcell_none<T>(cell: &RefCell<Option<T>>, msg: &str)
+where
+    T: fmt::Debug,
+{
+    match cell.try_borrow() {
+        Ok(opt) => {
+            if let Some(val) = failed::enforced(opt.as_ref()) {
+                failed::expect_none_failed(&msg, val);
+            }
+        }
+        Err(_) if failed::ENFORCE => failed::expect_refcell_none_borrowed(msg),
+        Err(_) => {}
+    }
+}
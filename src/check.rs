@@ -0,0 +1,100 @@
+use core::fmt;
+
+use crate::failed;
+
+/// A configurable check expecting an [`Option`] to be [`None`], returned by
+/// [`UnwrapNone::none_check`](crate::UnwrapNone::none_check).
+///
+/// The options only describe the panic message, which is built if
+/// [`assert`](NoneCheck::assert) finds a [`Some`].
+///
+/// # Examples
+///
+/// ```
+/// use std::panic;
+///
+/// use unwrap_none::UnwrapNone;
+///
+/// None::<u32>.none_check().context("importing users").assert();
+///
+/// # #[cfg(feature = "enforce")] {
+/// let err = panic::catch_unwind(|| {
+///     Some(vec![1, 2]).none_check().context("importing users").with_key(&3).pretty().assert()
+/// })
+/// .unwrap_err();
+/// let msg = err.downcast_ref::<String>().unwrap();
+/// assert_eq!(msg, "while importing users: expected None for key 3: [\n    1,\n    2,\n]");
+///
+/// let err = panic::catch_unwind(|| {
+///     Some("hunter2").none_check().with_key(&"password").redacted().assert()
+/// })
+/// .unwrap_err();
+/// let msg = err.downcast_ref::<String>().unwrap();
+/// assert_eq!(msg, "expected None for key \"password\"");
+/// # }
+/// ```
+#[must_use = "the check is only performed by `assert`"]
+pub struct NoneCheck<'a, T> {
+    opt: Option<T>,
+    context: Option<&'a str>,
+    key: Option<&'a dyn fmt::Debug>,
+    pretty: bool,
+    redacted: bool,
+}
+
+impl<'a, T> NoneCheck<'a, T> {
+    pub(crate) fn new(opt: Option<T>) -> Self {
+        Self {
+            opt,
+            context: None,
+            key: None,
+            pretty: false,
+            redacted: false,
+        }
+    }
+
+    /// Prefixes the panic message with `while {context}: `.
+    pub fn context(mut self, context: &'a str) -> Self {
+        self.context = Some(context);
+        self
+    }
+
+    /// Includes the key the option was looked up or inserted with in the
+    /// panic message.
+    pub fn with_key<K>(mut self, key: &'a K) -> Self
+    where
+        K: fmt::Debug,
+    {
+        self.key = Some(key);
+        self
+    }
+
+    /// Pretty-prints the content of the [`Some`] with `{:#?}`.
+    pub fn pretty(mut self) -> Self {
+        self.pretty = true;
+        self
+    }
+
+    /// Leaves the content of the [`Some`] out of the panic message.
+    pub fn redacted(mut self) -> Self {
+        self.redacted = true;
+        self
+    }
+
+    /// Consumes the check while expecting [`None`] and returning nothing.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is a [`Some`], with a panic message as configured.
+    #[inline]
+    #[track_caller]
+    pub fn assert(self)
+    where
+        T: fmt::Debug,
+    {
+        if let Some(val) = failed::enforced(self.opt) {
+            let value: Option<&dyn fmt::Debug> = if self.redacted { None } else { Some(&val) };
+            failed::none_check_failed(self.context, self.key, value, self.pretty);
+        }
+    }
+}
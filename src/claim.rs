@@ -0,0 +1,76 @@
+//! Assertion macros matching the [`claim`] and [`claims`] crates.
+//!
+//! These use the same names, return values and panic messages as their
+//! counterparts, so they can replace them in existing test suites.
+//!
+//! Available with the `claim` feature.
+//!
+//! [`claim`]: https://docs.rs/claim
+//! [`claims`]: https://docs.rs/claims
+
+/// Asserts that an expression evaluates to [`None`].
+///
+/// Like the macro of `claim`, this evaluates to `()`. Unlike
+/// [`unwrap_none::assert_none!`](crate::assert_none!), it consumes the
+/// expression.
+///
+/// # Panics
+///
+/// Panics if the expression evaluates to a [`Some`], with the panic message
+/// ``assertion failed, expected None, got Some({:?})``, followed by `: ` and
+/// the formatted message if one is passed.
+///
+/// # Examples
+///
+/// ```
+/// use unwrap_none::claim::assert_none;
+///
+/// assert_none!(None::<i32>);
+///
+/// // It can be used as the tail expression of a block returning `()`.
+/// fn check(slot: Option<i32>) {
+///     assert_none!(slot)
+/// }
+/// check(None);
+/// ```
+///
+/// ```should_panic
+/// use unwrap_none::claim::assert_none;
+///
+/// // This panics with "assertion failed, expected None, got Some(1)".
+/// assert_none!(Some(1));
+/// # #[cfg(not(feature = "enforce"))]
+/// # panic!("the checks are disabled");
+/// ```
+///
+/// ```
+/// use std::panic;
+///
+/// use unwrap_none::claim::assert_none;
+///
+/// # #[cfg(feature = "enforce")] {
+/// let err = panic::catch_unwind(|| assert_none!(Some(1), "slot {} is busy", 3)).unwrap_err();
+/// assert_eq!(
+///     err.downcast_ref::<String>().unwrap(),
+///     "assertion failed, expected None, got Some(1): slot 3 is busy",
+/// );
+/// # }
+/// ```
+#[doc(inline)]
+pub use crate::__claim_assert_none as assert_none;
+
+/// Asserts that an expression evaluates to [`None`] if debug assertions are
+/// enabled.
+///
+/// This is to [`assert_none!`] what [`debug_assert!`] is to [`assert!`]. With
+/// debug assertions disabled the expression is not evaluated.
+///
+/// # Examples
+///
+/// ```
+/// use unwrap_none::claim::debug_assert_none;
+///
+/// debug_assert_none!(None::<i32>);
+/// ```
+#[doc(inline)]
+pub use crate::__claim_debug_assert_none as debug_assert_none;
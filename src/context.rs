@@ -0,0 +1,101 @@
+use core::cell::RefCell;
+use core::fmt;
+use core::marker::PhantomData;
+
+use std::string::String;
+use std::vec::Vec;
+
+std::thread_local! {
+    static CONTEXT: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Pushes a context onto the current thread, which is prefixed to the panic
+/// messages of this crate until the returned guard is dropped.
+///
+/// This adds breadcrumbs like `while importing users: ` to panic messages
+/// without passing a message to every call. Nested contexts are prefixed in
+/// the order they were pushed. Guards should be dropped in reverse order,
+/// which happens automatically if they are bound to variables in nested
+/// scopes.
+///
+/// Available with the `std` feature.
+///
+/// # Examples
+///
+/// ```
+/// use std::panic;
+///
+/// use unwrap_none::{push_none_context, UnwrapNone};
+///
+/// # #[cfg(feature = "enforce")] {
+/// let err = panic::catch_unwind(|| {
+///     let _users = push_none_context("importing users");
+///     let _row = push_none_context(format!("reading row {}", 3));
+///     Some(5).expect_none("duplicate id");
+/// })
+/// .unwrap_err();
+/// let msg = err.downcast_ref::<String>().unwrap();
+/// # #[cfg(not(feature = "minimal-panic"))]
+/// assert_eq!(msg, "while importing users: while reading row 3: duplicate id: 5");
+///
+/// // The context is removed once the guards are dropped.
+/// let err = panic::catch_unwind(|| Some(5).expect_none("duplicate id")).unwrap_err();
+/// let msg = err.downcast_ref::<String>().unwrap();
+/// assert!(msg.starts_with("duplicate id"));
+/// # }
+/// ```
+pub fn push_none_context<C>(context: C) -> NoneContextGuard
+where
+    C: Into<String>,
+{
+    let context = context.into();
+    CONTEXT.with(|stack| stack.borrow_mut().push(context));
+    NoneContextGuard {
+        _not_send: PhantomData,
+    }
+}
+
+/// The guard returned by [`push_none_context`], which removes the context
+/// when dropped.
+///
+/// Available with the `std` feature.
+#[must_use = "the context is removed immediately if the guard is not kept"]
+pub struct NoneContextGuard {
+    // The context belongs to the thread that pushed it.
+    _not_send: PhantomData<*const ()>,
+}
+
+impl fmt::Debug for NoneContextGuard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NoneContextGuard").finish_non_exhaustive()
+    }
+}
+
+impl Drop for NoneContextGuard {
+    fn drop(&mut self) {
+        // The thread local may already be gone if the guard is dropped during
+        // thread teardown, in which case there is nothing left to remove.
+        let _ = CONTEXT.try_with(|stack| stack.borrow_mut().pop());
+    }
+}
+
+/// Formats the context of the current thread, if any.
+pub(crate) struct Current;
+
+impl fmt::Display for Current {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        CONTEXT
+            .try_with(|stack| {
+                // Losing the context is better than panicking while
+                // panicking, should this ever be reentered.
+                let Ok(stack) = stack.try_borrow() else {
+                    return Ok(());
+                };
+                for context in stack.iter() {
+                    write!(f, "while {}: ", context)?;
+                }
+                Ok(())
+            })
+            .unwrap_or(Ok(()))
+    }
+}
@@ -0,0 +1,58 @@
+use core::fmt;
+use std::string::String;
+
+/// The diagnostic returned by
+/// [`UnwrapNone::try_unwrap_none_diagnostic`](crate::UnwrapNone::try_unwrap_none_diagnostic),
+/// holding the formatted content of the unexpected [`Some`].
+///
+/// Available with the `miette` feature.
+///
+/// # Examples
+///
+/// ```
+/// use unwrap_none::{UnwrapNone, UnwrapNoneDiagnostic};
+///
+/// let report = Some(5).try_unwrap_none_diagnostic().unwrap_err();
+/// assert_eq!(report.to_string(), "expected None, got Some(5)");
+/// assert_eq!(report.code().unwrap().to_string(), "unwrap_none::unexpected_some");
+/// assert_eq!(report.help().unwrap().to_string(), "expected this slot to be empty");
+///
+/// let diagnostic = report.downcast_ref::<UnwrapNoneDiagnostic>().unwrap();
+/// assert_eq!(diagnostic.value(), "5");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnwrapNoneDiagnostic {
+    value: String,
+}
+
+impl UnwrapNoneDiagnostic {
+    pub(crate) fn new(value: &dyn fmt::Debug) -> Self {
+        Self {
+            value: std::format!("{:?}", value),
+        }
+    }
+
+    /// Returns the [`Debug`](fmt::Debug) representation of the unexpected
+    /// value.
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+}
+
+impl fmt::Display for UnwrapNoneDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "expected None, got Some({})", self.value)
+    }
+}
+
+impl std::error::Error for UnwrapNoneDiagnostic {}
+
+impl miette::Diagnostic for UnwrapNoneDiagnostic {
+    fn code<'a>(&'a self) -> Option<std::boxed::Box<dyn fmt::Display + 'a>> {
+        Some(std::boxed::Box::new("unwrap_none::unexpected_some"))
+    }
+
+    fn help<'a>(&'a self) -> Option<std::boxed::Box<dyn fmt::Display + 'a>> {
+        Some(std::boxed::Box::new("expected this slot to be empty"))
+    }
+}
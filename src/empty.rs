@@ -0,0 +1,609 @@
+use core::fmt;
+
+use crate::failed::{panic_in_context, ENFORCE};
+
+const UNWRAP_EMPTY_MSG: &str = "called `UnwrapEmpty::unwrap_empty()` on a non-empty value";
+
+/// Expects a collection to be empty.
+pub trait UnwrapEmpty {
+    /// Consumes `self` while expecting it to be empty and returning nothing.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `self` is not empty, with a panic message including the
+    /// passed message, the length of `self` and its contents.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use unwrap_none::UnwrapEmpty;
+    ///
+    /// let leftovers: &[u8] = &[];
+    /// leftovers.expect_empty("unconsumed input");
+    /// ```
+    ///
+    /// ```should_panic
+    /// use unwrap_none::UnwrapEmpty;
+    ///
+    /// let leftovers = vec![1, 2, 3];
+    /// leftovers.expect_empty("unconsumed input");
+    /// # #[cfg(not(feature = "enforce"))]
+    /// # panic!("the checks are disabled");
+    /// ```
+    fn expect_empty(self, msg: &str);
+
+    /// Consumes `self` while expecting it to be empty and returning nothing.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `self` is not empty, with a panic message including the
+    /// length of `self` and its contents.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use unwrap_none::UnwrapEmpty;
+    ///
+    /// let leftovers: Vec<u8> = Vec::new();
+    /// leftovers.unwrap_empty();
+    /// ```
+    ///
+    /// ```
+    /// use std::panic;
+    ///
+    /// use unwrap_none::UnwrapEmpty;
+    ///
+    /// let leftovers = [1, 2, 3];
+    /// # #[cfg(feature = "enforce")] {
+    /// let err = panic::catch_unwind(|| leftovers.unwrap_empty()).unwrap_err();
+    /// let msg = err.downcast_ref::<String>().unwrap();
+    /// assert!(msg.ends_with("length 3: [1, 2, 3]"));
+    /// # }
+    /// ```
+    fn unwrap_empty(self);
+}
+
+impl<T> UnwrapEmpty for &[T]
+where
+    T: fmt::Debug,
+{
+    #[inline]
+    #[track_caller]
+    fn expect_empty(self, msg: &str) {
+        if ENFORCE && !self.is_empty() {
+            expect_empty_failed(msg, self.len(), &self);
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn unwrap_empty(self) {
+        self.expect_empty(UNWRAP_EMPTY_MSG);
+    }
+}
+
+/// Available with the `alloc` feature.
+#[cfg(feature = "alloc")]
+impl<T> UnwrapEmpty for &alloc::vec::Vec<T>
+where
+    T: fmt::Debug,
+{
+    #[inline]
+    #[track_caller]
+    fn expect_empty(self, msg: &str) {
+        self.as_slice().expect_empty(msg);
+    }
+
+    #[inline]
+    #[track_caller]
+    fn unwrap_empty(self) {
+        self.as_slice().unwrap_empty();
+    }
+}
+
+impl UnwrapEmpty for &str {
+    /// The length is given in bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use unwrap_none::UnwrapEmpty;
+    ///
+    /// let (_, remainder) = "key=value".split_at(9);
+    /// remainder.expect_empty("trailing data");
+    /// ```
+    ///
+    /// ```
+    /// use std::panic;
+    ///
+    /// use unwrap_none::UnwrapEmpty;
+    ///
+    /// let (_, remainder) = "key=value;".split_at(9);
+    /// # #[cfg(feature = "enforce")] {
+    /// let err = panic::catch_unwind(|| remainder.expect_empty("trailing data")).unwrap_err();
+    /// let msg = err.downcast_ref::<String>().unwrap();
+    /// assert_eq!(msg, r#"trailing data: length 1: ";""#);
+    /// # }
+    /// ```
+    #[inline]
+    #[track_caller]
+    fn expect_empty(self, msg: &str) {
+        if ENFORCE && !self.is_empty() {
+            expect_empty_failed(msg, self.len(), &self);
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn unwrap_empty(self) {
+        self.expect_empty(UNWRAP_EMPTY_MSG);
+    }
+}
+
+/// Available with the `alloc` feature.
+#[cfg(feature = "alloc")]
+impl UnwrapEmpty for &alloc::string::String {
+    #[inline]
+    #[track_caller]
+    fn expect_empty(self, msg: &str) {
+        self.as_str().expect_empty(msg);
+    }
+
+    #[inline]
+    #[track_caller]
+    fn unwrap_empty(self) {
+        self.as_str().unwrap_empty();
+    }
+}
+
+/// Available with the `alloc` feature.
+///
+/// Only the first few entries are included in the panic message.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::BTreeMap;
+/// use std::panic;
+///
+/// use unwrap_none::UnwrapEmpty;
+///
+/// let cache: BTreeMap<_, _> = (0..1000).map(|i| (i, i * i)).collect();
+/// # #[cfg(feature = "enforce")] {
+/// let err = panic::catch_unwind(|| cache.expect_empty("residual entries")).unwrap_err();
+/// let msg = err.downcast_ref::<String>().unwrap();
+/// assert_eq!(
+///     msg,
+///     "residual entries: length 1000: \
+///      {0: 0, 1: 1, 2: 4, 3: 9, 4: 16, 5: 25, 6: 36, 7: 49, ..}",
+/// );
+/// # }
+/// ```
+#[cfg(feature = "alloc")]
+impl<K, V> UnwrapEmpty for &alloc::collections::BTreeMap<K, V>
+where
+    K: fmt::Debug,
+    V: fmt::Debug,
+{
+    #[inline]
+    #[track_caller]
+    fn expect_empty(self, msg: &str) {
+        if ENFORCE && !self.is_empty() {
+            expect_empty_failed(msg, self.len(), &MapSample(self.iter(), self.len()));
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn unwrap_empty(self) {
+        self.expect_empty(UNWRAP_EMPTY_MSG);
+    }
+}
+
+/// Available with the `alloc` feature.
+///
+/// Only the first few items are included in the panic message.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::BTreeSet;
+/// use std::panic;
+///
+/// use unwrap_none::UnwrapEmpty;
+///
+/// let mut pending = BTreeSet::from([3, 1, 2]);
+/// # #[cfg(feature = "enforce")] {
+/// let err = panic::catch_unwind(|| pending.unwrap_empty()).unwrap_err();
+/// let msg = err.downcast_ref::<String>().unwrap();
+/// assert!(msg.ends_with("length 3: {1, 2, 3}"));
+///
+/// pending.extend(4..=20);
+/// let err = panic::catch_unwind(|| pending.unwrap_empty()).unwrap_err();
+/// let msg = err.downcast_ref::<String>().unwrap();
+/// assert!(msg.ends_with("length 20: {1, 2, 3, 4, 5, 6, 7, 8, ..}"));
+///
+/// pending.clear();
+/// pending.expect_empty("residual entries");
+/// # }
+/// ```
+#[cfg(feature = "alloc")]
+impl<T> UnwrapEmpty for &alloc::collections::BTreeSet<T>
+where
+    T: fmt::Debug,
+{
+    #[inline]
+    #[track_caller]
+    fn expect_empty(self, msg: &str) {
+        if ENFORCE && !self.is_empty() {
+            expect_empty_failed(msg, self.len(), &SetSample(self.iter(), self.len()));
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn unwrap_empty(self) {
+        self.expect_empty(UNWRAP_EMPTY_MSG);
+    }
+}
+
+/// Available with the `std` feature.
+///
+/// Only the first few entries are included in the panic message.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashMap;
+/// use std::panic;
+///
+/// use unwrap_none::UnwrapEmpty;
+///
+/// let mut cache = HashMap::new();
+/// cache.expect_empty("residual entries");
+///
+/// cache.insert("key", 1);
+/// # #[cfg(feature = "enforce")] {
+/// let err = panic::catch_unwind(|| cache.expect_empty("residual entries")).unwrap_err();
+/// let msg = err.downcast_ref::<String>().unwrap();
+/// assert_eq!(msg, r#"residual entries: length 1: {"key": 1}"#);
+/// # }
+///
+/// let cache: HashMap<u32, u32> = (0..1000).map(|i| (i, i)).collect();
+/// # #[cfg(feature = "enforce")] {
+/// let err = panic::catch_unwind(|| cache.expect_empty("residual entries")).unwrap_err();
+/// let msg = err.downcast_ref::<String>().unwrap();
+/// let sample = msg.split_once("length 1000: ").unwrap().1;
+/// assert!(sample.starts_with('{') && sample.ends_with(", ..}"));
+/// assert_eq!(sample.matches(", ").count(), 8);
+/// # }
+/// ```
+#[cfg(feature = "std")]
+impl<K, V, S> UnwrapEmpty for &std::collections::HashMap<K, V, S>
+where
+    K: fmt::Debug,
+    V: fmt::Debug,
+{
+    #[inline]
+    #[track_caller]
+    fn expect_empty(self, msg: &str) {
+        if ENFORCE && !self.is_empty() {
+            expect_empty_failed(msg, self.len(), &MapSample(self.iter(), self.len()));
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn unwrap_empty(self) {
+        self.expect_empty(UNWRAP_EMPTY_MSG);
+    }
+}
+
+/// Available with the `std` feature.
+///
+/// Only the first few items are included in the panic message.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashSet;
+/// use std::panic;
+///
+/// use unwrap_none::UnwrapEmpty;
+///
+/// let pending: HashSet<u32> = (0..1000).collect();
+/// # #[cfg(feature = "enforce")] {
+/// let err = panic::catch_unwind(|| pending.unwrap_empty()).unwrap_err();
+/// let msg = err.downcast_ref::<String>().unwrap();
+/// let sample = msg.split_once("length 1000: ").unwrap().1;
+/// assert!(sample.starts_with('{') && sample.ends_with(", ..}"));
+/// assert_eq!(sample.matches(", ").count(), 8);
+/// # }
+/// ```
+#[cfg(feature = "std")]
+impl<T, S> UnwrapEmpty for &std::collections::HashSet<T, S>
+where
+    T: fmt::Debug,
+{
+    #[inline]
+    #[track_caller]
+    fn expect_empty(self, msg: &str) {
+        if ENFORCE && !self.is_empty() {
+            expect_empty_failed(msg, self.len(), &SetSample(self.iter(), self.len()));
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn unwrap_empty(self) {
+        self.expect_empty(UNWRAP_EMPTY_MSG);
+    }
+}
+
+/// Available with the `smallvec` feature.
+///
+/// Only the first few items are included in the panic message, since the
+/// vector may have spilled onto the heap.
+///
+/// # Examples
+///
+/// ```
+/// use std::panic;
+///
+/// use smallvec::SmallVec;
+/// use unwrap_none::UnwrapEmpty;
+///
+/// let mut queue = SmallVec::<[u32; 4]>::new();
+/// queue.expect_empty("queue is not drained");
+///
+/// queue.extend(0..20);
+/// # #[cfg(feature = "enforce")] {
+/// let err = panic::catch_unwind(|| queue.expect_empty("queue is not drained")).unwrap_err();
+/// let msg = err.downcast_ref::<String>().unwrap();
+/// assert_eq!(msg, "queue is not drained: length 20: [0, 1, 2, 3, 4, 5, 6, 7, ..]");
+/// # }
+/// ```
+#[cfg(feature = "smallvec")]
+impl<A> UnwrapEmpty for &smallvec::SmallVec<A>
+where
+    A: smallvec::Array,
+    A::Item: fmt::Debug,
+{
+    #[inline]
+    #[track_caller]
+    fn expect_empty(self, msg: &str) {
+        if ENFORCE && !self.is_empty() {
+            expect_empty_failed(msg, self.len(), &Sample(self.iter(), self.len()));
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn unwrap_empty(self) {
+        self.expect_empty(UNWRAP_EMPTY_MSG);
+    }
+}
+
+/// Available with the `arrayvec` feature.
+///
+/// Only the first few items are included in the panic message.
+///
+/// # Examples
+///
+/// ```
+/// use std::panic;
+///
+/// use arrayvec::ArrayVec;
+/// use unwrap_none::UnwrapEmpty;
+///
+/// let mut queue = ArrayVec::<u32, 4>::new();
+/// queue.unwrap_empty();
+///
+/// queue.push(7);
+/// # #[cfg(feature = "enforce")] {
+/// let err = panic::catch_unwind(|| queue.unwrap_empty()).unwrap_err();
+/// let msg = err.downcast_ref::<String>().unwrap();
+/// assert!(msg.ends_with("length 1: [7]"));
+/// # }
+/// ```
+#[cfg(feature = "arrayvec")]
+impl<T, const CAP: usize> UnwrapEmpty for &arrayvec::ArrayVec<T, CAP>
+where
+    T: fmt::Debug,
+{
+    #[inline]
+    #[track_caller]
+    fn expect_empty(self, msg: &str) {
+        if ENFORCE && !self.is_empty() {
+            expect_empty_failed(msg, self.len(), &Sample(self.iter(), self.len()));
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn unwrap_empty(self) {
+        self.expect_empty(UNWRAP_EMPTY_MSG);
+    }
+}
+
+/// Formats at most [`SAMPLE_LEN`] items of an iterator over a collection of
+/// the given length as a list.
+#[cfg(any(feature = "smallvec", feature = "arrayvec"))]
+struct Sample<I>(I, usize);
+
+/// Like [`Sample`], but formats the items as a set.
+#[cfg(feature = "alloc")]
+struct SetSample<I>(I, usize);
+
+/// Like [`Sample`], but formats the key-value pairs as a map.
+#[cfg(feature = "alloc")]
+struct MapSample<I>(I, usize);
+
+/// The amount of items included in the panic message of large collections.
+#[cfg(any(feature = "alloc", feature = "smallvec", feature = "arrayvec"))]
+const SAMPLE_LEN: usize = 8;
+
+#[cfg(any(feature = "smallvec", feature = "arrayvec"))]
+impl<I> fmt::Debug for Sample<I>
+where
+    I: Iterator + Clone,
+    I::Item: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut list = f.debug_list();
+        list.entries(self.0.clone().take(SAMPLE_LEN));
+        if self.1 > SAMPLE_LEN {
+            list.finish_non_exhaustive()
+        } else {
+            list.finish()
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<I> fmt::Debug for SetSample<I>
+where
+    I: Iterator + Clone,
+    I::Item: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut set = f.debug_set();
+        set.entries(self.0.clone().take(SAMPLE_LEN));
+        if self.1 > SAMPLE_LEN {
+            set.finish_non_exhaustive()
+        } else {
+            set.finish()
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<I, K, V> fmt::Debug for MapSample<I>
+where
+    I: Iterator<Item = (K, V)> + Clone,
+    K: fmt::Debug,
+    V: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut map = f.debug_map();
+        map.entries(self.0.clone().take(SAMPLE_LEN));
+        if self.1 > SAMPLE_LEN {
+            map.finish_non_exhaustive()
+        } else {
+            map.finish()
+        }
+    }
+}
+
+/// Expects an [`Iterator`] to not yield any items.
+///
+/// These methods consume the iterator. If it is not empty, at most two items
+/// are pulled from it to build the panic message and the remaining items are
+/// dropped along with the iterator.
+pub trait ExpectEmptyIter {
+    /// Consumes `self` while expecting it to not yield any items and returning
+    /// nothing.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `self` yields an item, with a panic message including the
+    /// passed message and the first item, as well as whether more items
+    /// follow it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use unwrap_none::ExpectEmptyIter;
+    ///
+    /// let mut words = "hello world".split(' ');
+    /// assert_eq!(words.next(), Some("hello"));
+    /// assert_eq!(words.next(), Some("world"));
+    /// words.expect_empty("unexpected word");
+    /// ```
+    ///
+    /// ```
+    /// use std::panic;
+    ///
+    /// use unwrap_none::ExpectEmptyIter;
+    ///
+    /// let mut words = "hello big wide world".split(' ');
+    /// assert_eq!(words.next(), Some("hello"));
+    /// # #[cfg(feature = "enforce")] {
+    /// let err = panic::catch_unwind(move || words.expect_empty("unexpected word")).unwrap_err();
+    /// let msg = err.downcast_ref::<String>().unwrap();
+    /// assert_eq!(msg, r#"unexpected word: "big" and more"#);
+    /// # }
+    /// ```
+    fn expect_empty(self, msg: &str);
+
+    /// Consumes `self` while expecting it to not yield any items and returning
+    /// nothing.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `self` yields an item, with a panic message including the
+    /// first item, as well as whether more items follow it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use unwrap_none::ExpectEmptyIter;
+    ///
+    /// (0..0).unwrap_empty();
+    /// ```
+    ///
+    /// ```should_panic
+    /// use unwrap_none::ExpectEmptyIter;
+    ///
+    /// (0..1).unwrap_empty();
+    /// # #[cfg(not(feature = "enforce"))]
+    /// # panic!("the checks are disabled");
+    /// ```
+    fn unwrap_empty(self);
+}
+
+impl<I> ExpectEmptyIter for I
+where
+    I: Iterator,
+    I::Item: fmt::Debug,
+{
+    #[inline]
+    #[track_caller]
+    fn expect_empty(mut self, msg: &str) {
+        if !ENFORCE {
+            return;
+        }
+        if let Some(first) = self.next() {
+            let more = self.next().is_some();
+            expect_empty_iter_failed(msg, &first, more);
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn unwrap_empty(self) {
+        self.expect_empty("called `ExpectEmptyIter::unwrap_empty()` on a non-empty iterator");
+    }
+}
+
+#[inline(never)]
+#[cold]
+#[track_caller]
+fn expect_empty_failed(msg: &str, len: usize, contents: &dyn fmt::Debug) -> ! {
+    #[cfg(feature = "std")]
+    crate::hook::on_unexpected_some(contents);
+    panic_in_context!("{}: length {}: {:?}", msg, len, contents)
+}
+
+#[inline(never)]
+#[cold]
+#[track_caller]
+fn expect_empty_iter_failed(msg: &str, first: &dyn fmt::Debug, more: bool) -> ! {
+    #[cfg(feature = "std")]
+    crate::hook::on_unexpected_some(first);
+    if more {
+        panic_in_context!("{}: {:?} and more", msg, first)
+    } else {
+        panic_in_context!("{}: {:?}", msg, first)
+    }
+}
@@ -0,0 +1,107 @@
+use core::fmt;
+
+/// The error returned by
+/// [`UnwrapNone::try_unwrap_none_err`](crate::UnwrapNone::try_unwrap_none_err),
+/// wrapping the unexpected content of a [`Some`].
+///
+/// The error is [`Send`] and [`Sync`] if `T` is. Its field is private, so it
+/// can only be built with [`UnwrapNoneError::new`].
+///
+/// Available with the `std` feature.
+///
+/// # Examples
+///
+/// ```
+/// use std::error::Error;
+///
+/// use unwrap_none::UnwrapNone;
+///
+/// let err: Box<dyn Error> = Some(5).try_unwrap_none_err().unwrap_err().into();
+/// assert_eq!(err.to_string(), "expected None, got Some(5)");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnwrapNoneError<T> {
+    value: T,
+}
+
+impl<T> UnwrapNoneError<T> {
+    /// Wraps an unexpected value.
+    ///
+    /// This is useful to build the error in e.g. [`From`] implementations of
+    /// other error types.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use unwrap_none::UnwrapNoneError;
+    ///
+    /// let err = UnwrapNoneError::new("stale");
+    /// assert_eq!(err.to_string(), "expected None, got Some(\"stale\")");
+    /// assert_eq!(err.into_inner(), "stale");
+    /// ```
+    pub fn new(value: T) -> Self {
+        Self { value }
+    }
+
+    /// Returns a reference to the unexpected value.
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+
+    /// Consumes the error, returning the unexpected value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use unwrap_none::UnwrapNone;
+    ///
+    /// let err = Some(vec![1, 2]).try_unwrap_none_err().unwrap_err();
+    /// assert_eq!(err.into_inner(), [1, 2]);
+    /// ```
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T> fmt::Display for UnwrapNoneError<T>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "expected None, got Some({:?})", self.value)
+    }
+}
+
+impl<T> std::error::Error for UnwrapNoneError<T> where T: fmt::Debug {}
+
+/// Serializes the error as a struct with the unexpected value under `value`
+/// and the constant tag `"unexpected_some"` under `kind`.
+///
+/// Available with the `serde` feature.
+///
+/// # Examples
+///
+/// ```
+/// use unwrap_none::UnwrapNone;
+///
+/// let err = Some(5).try_unwrap_none_err().unwrap_err();
+/// let json = serde_json::to_value(&err).unwrap();
+/// assert_eq!(json, serde_json::json!({ "value": 5, "kind": "unexpected_some" }));
+/// ```
+#[cfg(feature = "serde")]
+impl<T> serde::Serialize for UnwrapNoneError<T>
+where
+    T: serde::Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+
+        let mut state = serializer.serialize_struct("UnwrapNoneError", 2)?;
+        state.serialize_field("value", &self.value)?;
+        state.serialize_field("kind", "unexpected_some")?;
+        state.end()
+    }
+}
@@ -0,0 +1,128 @@
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::failed::{panic_in_context, ENFORCE};
+
+/// Collects the results of many operations returning an [`Option`] and
+/// expects a certain number of them to be [`Some`].
+///
+/// This is more flexible than checking every result individually, e.g. when
+/// seeding a map where a known number of keys is expected to be overwritten.
+///
+/// Available with the `alloc` feature.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// use unwrap_none::NoneExpectations;
+///
+/// let mut map = HashMap::new();
+/// let mut expectations = NoneExpectations::new();
+/// for i in -3..=3 {
+///     expectations.record(map.insert(i * i, i));
+/// }
+/// // -1, -2 and -3 are overwritten by 1, 2 and 3.
+/// expectations.expect_exactly(3);
+/// ```
+///
+/// ```
+/// use std::collections::HashMap;
+/// use std::panic;
+///
+/// use unwrap_none::NoneExpectations;
+///
+/// let mut map = HashMap::new();
+/// let mut expectations = NoneExpectations::new();
+/// for i in [1, 2, -2] {
+///     expectations.record(map.insert(i * i, i));
+/// }
+/// # #[cfg(feature = "enforce")] {
+/// let err = panic::catch_unwind(|| expectations.expect_zero()).unwrap_err();
+/// assert_eq!(
+///     err.downcast_ref::<String>().unwrap(),
+///     "expected 0 `Some` values, got 1: [2]",
+/// );
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NoneExpectations<T> {
+    values: Vec<T>,
+}
+
+impl<T> NoneExpectations<T> {
+    /// Creates a new, empty collector.
+    pub const fn new() -> Self {
+        Self { values: Vec::new() }
+    }
+
+    /// Records the result of a single operation, keeping the content of a
+    /// [`Some`].
+    #[inline]
+    pub fn record(&mut self, opt: Option<T>) -> &mut Self {
+        if let Some(val) = opt {
+            self.values.push(val);
+        }
+        self
+    }
+
+    /// Returns the number of [`Some`] values recorded so far.
+    pub fn count(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns the [`Some`] values recorded so far, in order.
+    pub fn values(&self) -> &[T] {
+        &self.values
+    }
+
+    /// Consumes `self` while expecting no [`Some`] values to be recorded.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any [`Some`] value was recorded, with a panic message
+    /// including all recorded values.
+    #[track_caller]
+    pub fn expect_zero(self)
+    where
+        T: fmt::Debug,
+    {
+        self.expect_exactly(0);
+    }
+
+    /// Consumes `self` while expecting exactly `n` [`Some`] values to be
+    /// recorded.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a different number of [`Some`] values was recorded, with a
+    /// panic message including all recorded values.
+    #[track_caller]
+    pub fn expect_exactly(self, n: usize)
+    where
+        T: fmt::Debug,
+    {
+        if ENFORCE && self.values.len() != n {
+            expect_exactly_failed(n, self.values.len(), &self.values);
+        }
+    }
+}
+
+impl<T> Default for NoneExpectations<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[inline(never)]
+#[cold]
+#[track_caller]
+fn expect_exactly_failed(expected: usize, count: usize, values: &dyn fmt::Debug) -> ! {
+    panic_in_context!(
+        "expected {} `Some` values, got {}: {:?}",
+        expected,
+        count,
+        values
+    )
+}
@@ -0,0 +1,139 @@
+use core::ops::Deref;
+
+use crate::{failed, MaybeDebug, UnwrapNone};
+
+/// An [`Option`] that is expected to be [`None`], for use with [`TryFrom`]
+/// and [`TryInto`].
+///
+/// Converting it into `()` succeeds if the option is [`None`], and fails with
+/// the content of the [`Some`] as error otherwise. This is the same as
+/// [`UnwrapNone::try_unwrap_none`](crate::UnwrapNone::try_unwrap_none), but
+/// works with generic code built on the standard conversion traits.
+///
+/// The target type usually can't be inferred, so it has to be spelled out,
+/// e.g. with `let (): () = ...` or `<()>::try_from(...)`.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// use unwrap_none::ExpectedNone;
+///
+/// fn insert_all(map: &mut HashMap<i32, i32>, values: &[i32]) -> Result<(), i32> {
+///     for &i in values {
+///         let (): () = ExpectedNone(map.insert(i * i, i)).try_into()?;
+///     }
+///     Ok(())
+/// }
+///
+/// let mut map = HashMap::new();
+/// assert_eq!(insert_all(&mut map, &[1, 2, 3]), Ok(()));
+///
+/// // `-2 * -2` collides with the `2 * 2` key inserted above.
+/// let mut map = HashMap::new();
+/// assert_eq!(insert_all(&mut map, &[1, 2, -2]), Err(2));
+/// ```
+///
+/// ```
+/// use unwrap_none::ExpectedNone;
+///
+/// #[derive(Debug, PartialEq)]
+/// struct Occupied(&'static str);
+///
+/// impl From<&'static str> for Occupied {
+///     fn from(old: &'static str) -> Self {
+///         Self(old)
+///     }
+/// }
+///
+/// fn check(slot: Option<&'static str>) -> Result<(), Occupied> {
+///     <()>::try_from(ExpectedNone(slot))?;
+///     Ok(())
+/// }
+///
+/// assert_eq!(check(None), Ok(()));
+/// assert_eq!(check(Some("stale")), Err(Occupied("stale")));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ExpectedNone<T>(pub Option<T>);
+
+impl<T> TryFrom<ExpectedNone<T>> for () {
+    type Error = T;
+
+    #[inline]
+    fn try_from(value: ExpectedNone<T>) -> Result<Self, T> {
+        match failed::observed(value.0) {
+            None => Ok(()),
+            Some(val) => Err(val),
+        }
+    }
+}
+
+/// An [`Option`] that is expected to be [`None`], for functions accepting
+/// anything that converts into it.
+///
+/// This lets generic code take `impl Into<AssertEmpty<T>>` and check it with
+/// consistent panic messages. The option can be inspected through [`Deref`]
+/// before asserting.
+///
+/// # Examples
+///
+/// ```
+/// use std::panic;
+///
+/// use unwrap_none::AssertEmpty;
+///
+/// fn release<S>(slot: S)
+/// where
+///     S: Into<AssertEmpty<u32>>,
+/// {
+///     let slot = slot.into();
+///     if slot.is_some() {
+///         eprintln!("releasing an occupied slot");
+///     }
+///     slot.assert("slot is still occupied");
+/// }
+///
+/// release(None);
+///
+/// # #[cfg(feature = "enforce")] {
+/// let err = panic::catch_unwind(|| release(Some(3))).unwrap_err();
+/// let msg = err.downcast_ref::<String>().unwrap();
+/// # #[cfg(not(feature = "minimal-panic"))]
+/// assert_eq!(msg, "slot is still occupied: 3");
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct AssertEmpty<T>(Option<T>);
+
+impl<T> AssertEmpty<T> {
+    /// Consumes `self` while expecting [`None`] and returning nothing.
+    ///
+    /// # Panics
+    ///
+    /// Panics like [`UnwrapNone::expect_none`] if the value is a [`Some`].
+    #[inline]
+    #[track_caller]
+    pub fn assert(self, msg: &str)
+    where
+        T: MaybeDebug,
+    {
+        self.0.expect_none(msg);
+    }
+}
+
+impl<T> From<Option<T>> for AssertEmpty<T> {
+    #[inline]
+    fn from(opt: Option<T>) -> Self {
+        Self(opt)
+    }
+}
+
+impl<T> Deref for AssertEmpty<T> {
+    type Target = Option<T>;
+
+    fn deref(&self) -> &Option<T> {
+        &self.0
+    }
+}
@@ -0,0 +1,465 @@
+//! Cold panic paths shared by all methods.
+//!
+//! Every function here is non-generic and only takes `&str`, `&dyn Debug` or
+//! `&dyn Display`, so the formatting machinery is compiled once instead of
+//! once per `T`. The generic methods only need to branch and call in here.
+//! New methods should add a helper following the same rule.
+
+use core::fmt;
+
+pub(crate) use crate::macros::{enforced, observed, ENFORCE};
+use crate::truncate;
+
+/// Panics like [`panic!`], prefixed with the context pushed with
+/// [`push_none_context`](crate::push_none_context) if the `std` feature is
+/// enabled.
+///
+/// With the `std` feature, the failure is also recorded for
+/// [`expect_none_strict`](crate::UnwrapNone::expect_none_strict).
+macro_rules! panic_in_context {
+    ($($arg:tt)+) => {{
+        #[cfg(feature = "std")]
+        crate::strict::record_failure();
+        #[cfg(feature = "std")]
+        panic!("{}{}", crate::context::Current, format_args!($($arg)+));
+        #[cfg(not(feature = "std"))]
+        panic!($($arg)+);
+    }};
+}
+pub(crate) use panic_in_context;
+
+// This is a separate function to reduce the code size of .expect_none() itself.
+#[inline(never)]
+#[cold]
+#[track_caller]
+pub(crate) fn expect_none_failed(msg: &dyn fmt::Display, value: &dyn fmt::Debug) -> ! {
+    #[cfg(feature = "std")]
+    crate::hook::on_unexpected_some(value);
+    panic_in_context!("{}: {:?}", msg, value)
+}
+
+// Like expect_none_failed, but translates the message with the std feature.
+#[cfg(not(feature = "minimal-panic"))]
+#[inline(never)]
+#[cold]
+#[track_caller]
+pub(crate) fn expect_none_msg_failed(msg: &str, value: &dyn fmt::Debug) -> ! {
+    #[cfg(feature = "std")]
+    let msg = crate::translate::translate(msg);
+    expect_none_failed(&msg, value)
+}
+
+// Like none_failed, but translates the message with the std feature.
+#[cfg(feature = "minimal-panic")]
+#[inline(never)]
+#[cold]
+#[track_caller]
+pub(crate) fn none_msg_failed(msg: &str) -> ! {
+    #[cfg(feature = "std")]
+    none_failed(&crate::translate::translate(msg));
+    #[cfg(not(feature = "std"))]
+    none_failed(msg)
+}
+
+// Like expect_none_failed, but without ever touching the value.
+#[inline(never)]
+#[cold]
+#[track_caller]
+pub(crate) fn none_failed(msg: &str) -> ! {
+    panic_in_context!("{}", msg)
+}
+
+#[inline(never)]
+#[cold]
+#[track_caller]
+pub(crate) fn none_failed_display(msg: &dyn fmt::Display) -> ! {
+    panic_in_context!("{}", msg)
+}
+
+// Like expect_none_failed, but formats the value with Display instead.
+#[inline(never)]
+#[cold]
+#[track_caller]
+pub(crate) fn expect_none_failed_display(msg: &str, value: &dyn fmt::Display) -> ! {
+    #[cfg(feature = "std")]
+    crate::hook::on_unexpected_some(&DisplayAsDebug(value));
+    panic_in_context!("{}: {}", msg, value)
+}
+
+// Like expect_none_failed, but pretty-prints the value instead.
+#[inline(never)]
+#[cold]
+#[track_caller]
+pub(crate) fn expect_none_failed_pretty(msg: &str, value: &dyn fmt::Debug) -> ! {
+    #[cfg(feature = "std")]
+    crate::hook::on_unexpected_some(value);
+    panic_in_context!("{}: {:#?}", msg, value)
+}
+
+#[inline(never)]
+#[cold]
+#[track_caller]
+pub(crate) fn expect_none_failed_styled(
+    msg: &str,
+    value: &dyn fmt::Debug,
+    flags: crate::FormatFlags,
+) -> ! {
+    if flags.hide_value {
+        panic_in_context!("{}", msg);
+    }
+    #[cfg(feature = "std")]
+    crate::hook::on_unexpected_some(value);
+    match (flags.alternate, flags.width) {
+        (false, None) => panic_in_context!("{}: {:?}", msg, value),
+        (true, None) => panic_in_context!("{}: {:#?}", msg, value),
+        (false, Some(width)) => panic_in_context!("{}: {:width$?}", msg, value, width = width),
+        (true, Some(width)) => panic_in_context!("{}: {:#width$?}", msg, value, width = width),
+    }
+}
+
+#[inline(never)]
+#[cold]
+#[track_caller]
+pub(crate) fn unwrap_none_failed_truncated(value: &dyn fmt::Debug, max_len: usize) -> ! {
+    #[cfg(feature = "std")]
+    crate::hook::on_unexpected_some(value);
+    panic_in_context!(
+        "called `Option::unwrap_none_truncated()` on a `Some` value: {}",
+        truncate::Truncated { value, max_len }
+    )
+}
+
+#[inline(never)]
+#[cold]
+#[track_caller]
+pub(crate) fn unwrap_none_failed_typed(type_name: &str, value: &dyn fmt::Debug) -> ! {
+    #[cfg(feature = "std")]
+    crate::hook::on_unexpected_some(value);
+    panic_in_context!(
+        "called `Option::unwrap_none_typed()` on a `Some` value of type {}: {:?}",
+        type_name,
+        value
+    )
+}
+
+#[inline(never)]
+#[cold]
+#[track_caller]
+pub(crate) fn unwrap_none_failed_named(type_name: &str, name: &str) -> ! {
+    #[cfg(feature = "std")]
+    crate::hook::on_unexpected_some(&DisplayAsDebug(&name));
+    panic_in_context!(
+        "called `Option::unwrap_none_named()` on a `Some` value of type {}: {}",
+        type_name,
+        name
+    )
+}
+
+#[cfg(feature = "std")]
+#[inline(never)]
+#[cold]
+#[track_caller]
+pub(crate) fn unwrap_none_failed_backtrace(value: &dyn fmt::Debug) -> ! {
+    crate::hook::on_unexpected_some(value);
+    use std::backtrace::{Backtrace, BacktraceStatus};
+
+    const MSG: &str = "called `Option::unwrap_none_backtrace()` on a `Some` value";
+    let backtrace = Backtrace::capture();
+    if backtrace.status() == BacktraceStatus::Captured {
+        panic_in_context!("{}: {:?}\n\nbacktrace:\n{}", MSG, value, backtrace)
+    } else {
+        panic_in_context!("{}: {:?}", MSG, value)
+    }
+}
+
+// Lets values only implementing Display be passed to the hook.
+#[cfg(feature = "std")]
+pub(crate) struct DisplayAsDebug<'a>(pub(crate) &'a dyn fmt::Display);
+
+#[cfg(feature = "std")]
+impl fmt::Debug for DisplayAsDebug<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[cfg(feature = "std")]
+#[inline(never)]
+#[cold]
+#[track_caller]
+pub(crate) fn unwrap_none_failed_abort(value: &dyn fmt::Debug) -> ! {
+    crate::hook::on_unexpected_some(value);
+    std::eprintln!(
+        "called `Option::unwrap_none_or_abort()` on a `Some` value at {}: {:?}",
+        core::panic::Location::caller(),
+        value
+    );
+    std::process::abort()
+}
+
+#[cfg(feature = "std")]
+#[inline(never)]
+#[cold]
+#[track_caller]
+pub(crate) fn eprint_unexpected(value: &dyn fmt::Debug) {
+    std::eprintln!(
+        "expected None, got Some({:?}) at {}",
+        value,
+        core::panic::Location::caller()
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[inline(never)]
+#[cold]
+#[track_caller]
+pub(crate) fn unwrap_none_failed_boxed(type_name: &str) -> ! {
+    #[cfg(feature = "std")]
+    crate::hook::on_unexpected_some(&DisplayAsDebug(&type_name));
+    panic_in_context!(
+        "called `Option::unwrap_none_boxed()` on a `Some` value: Box<{}>",
+        type_name
+    )
+}
+
+#[cfg(feature = "alloc")]
+#[inline(never)]
+#[cold]
+#[track_caller]
+pub(crate) fn expect_upgraded_none_failed(msg: &str, strong_count: usize) -> ! {
+    #[cfg(feature = "std")]
+    crate::hook::on_unexpected_some(&strong_count);
+    panic_in_context!(
+        "{}: still alive with a strong count of {}",
+        msg,
+        strong_count
+    )
+}
+
+#[inline(never)]
+#[cold]
+#[track_caller]
+pub(crate) fn unwrap_none_failed_machine(value: &dyn fmt::Debug) -> ! {
+    #[cfg(feature = "std")]
+    crate::hook::on_unexpected_some(value);
+    #[cfg(feature = "std")]
+    crate::strict::record_failure();
+    panic!("{}Some({:?})", crate::EXPECT_NONE_PREFIX, value)
+}
+
+#[inline(never)]
+#[cold]
+#[track_caller]
+pub(crate) fn expect_none_failed_variant(msg: &str, variant: &str, value: &dyn fmt::Debug) -> ! {
+    #[cfg(feature = "std")]
+    crate::hook::on_unexpected_some(value);
+    panic_in_context!("{}: got `{}`: {:?}", msg, variant, value)
+}
+
+#[inline(never)]
+#[cold]
+pub(crate) fn write_none_failed(
+    w: &mut dyn fmt::Write,
+    prefix: &str,
+    value: &dyn fmt::Debug,
+) -> fmt::Result {
+    #[cfg(feature = "std")]
+    crate::hook::on_unexpected_some(value);
+    write!(w, "{}: {:?}", prefix, value)
+}
+
+#[inline(never)]
+#[cold]
+#[track_caller]
+pub(crate) fn expect_none_failed_written(written: Result<&str, fmt::Error>, prefix: &str) -> ! {
+    match written {
+        Ok(msg) => panic_in_context!("{}", msg),
+        Err(fmt::Error) => panic_in_context!("{}", prefix),
+    }
+}
+
+#[inline(never)]
+#[cold]
+#[track_caller]
+pub(crate) fn expect_none_at_failed(msg: &str, index: usize, value: &dyn fmt::Debug) -> ! {
+    #[cfg(feature = "std")]
+    crate::hook::on_unexpected_some(value);
+    panic_in_context!("{} at index {}: {:?}", msg, index, value)
+}
+
+#[inline(never)]
+#[cold]
+#[track_caller]
+pub(crate) fn expect_none_for_key_failed(
+    msg: &str,
+    key: &dyn fmt::Debug,
+    value: &dyn fmt::Debug,
+) -> ! {
+    #[cfg(feature = "std")]
+    crate::hook::on_unexpected_some(value);
+    panic_in_context!("{} for key {:?}: {:?}", msg, key, value)
+}
+
+#[inline(never)]
+#[cold]
+#[track_caller]
+pub(crate) fn expect_none_hashed_failed(msg: &str, hash: u64, value: &dyn fmt::Debug) -> ! {
+    #[cfg(feature = "std")]
+    crate::hook::on_unexpected_some(value);
+    panic_in_context!("{} for key with hash {:016x}: {:?}", msg, hash, value)
+}
+
+#[inline(never)]
+#[cold]
+#[track_caller]
+pub(crate) fn expect_none_coded_failed(code: &str, detail: &str, value: &dyn fmt::Debug) -> ! {
+    #[cfg(feature = "std")]
+    crate::hook::on_unexpected_some(value);
+    panic_in_context!("[{}] {}: Some({:?})", code, detail, value)
+}
+
+#[inline(never)]
+#[cold]
+#[track_caller]
+pub(crate) fn none_check_failed(
+    context: Option<&str>,
+    key: Option<&dyn fmt::Debug>,
+    value: Option<&dyn fmt::Debug>,
+    pretty: bool,
+) -> ! {
+    struct Message<'a> {
+        context: Option<&'a str>,
+        key: Option<&'a dyn fmt::Debug>,
+        value: Option<&'a dyn fmt::Debug>,
+        pretty: bool,
+    }
+
+    impl fmt::Display for Message<'_> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            if let Some(context) = self.context {
+                write!(f, "while {}: ", context)?;
+            }
+            f.write_str("expected None")?;
+            if let Some(key) = self.key {
+                write!(f, " for key {:?}", key)?;
+            }
+            match self.value {
+                Some(value) if self.pretty => write!(f, ": {:#?}", value),
+                Some(value) => write!(f, ": {:?}", value),
+                None => Ok(()),
+            }
+        }
+    }
+
+    #[cfg(feature = "std")]
+    if let Some(value) = value {
+        crate::hook::on_unexpected_some(value);
+    }
+    panic_in_context!(
+        "{}",
+        Message {
+            context,
+            key,
+            value,
+            pretty
+        }
+    )
+}
+
+#[inline(never)]
+#[cold]
+#[track_caller]
+pub(crate) fn expect_none_and_failed(msg: &str, value: Option<&dyn fmt::Debug>) -> ! {
+    match value {
+        Some(value) => {
+            #[cfg(feature = "std")]
+            crate::hook::on_unexpected_some(value);
+            panic_in_context!("{}: expected None, got Some({:?})", msg, value)
+        }
+        None => panic_in_context!("{}: additional check failed", msg),
+    }
+}
+
+#[cfg(feature = "std")]
+#[inline(never)]
+#[cold]
+pub(crate) fn none_on_drop_failed(
+    location: &core::panic::Location<'_>,
+    value: &dyn fmt::Debug,
+) -> ! {
+    crate::hook::on_unexpected_some(value);
+    panic_in_context!("slot guarded at {} is not None: {:?}", location, value)
+}
+
+#[cfg(feature = "std")]
+#[inline(never)]
+#[cold]
+#[track_caller]
+pub(crate) fn must_check_none_failed(
+    location: &core::panic::Location<'_>,
+    value: &dyn fmt::Debug,
+) -> ! {
+    crate::hook::on_unexpected_some(value);
+    panic_in_context!("value checked at {} is not None: {:?}", location, value)
+}
+
+#[cfg(feature = "std")]
+#[inline(never)]
+#[cold]
+#[track_caller]
+pub(crate) fn expect_none_once_failed(msg: &str) -> ! {
+    panic_in_context!("{}: called more than once", msg)
+}
+
+#[cfg(feature = "std")]
+#[inline(never)]
+#[cold]
+#[track_caller]
+pub(crate) fn expect_none_strict_failed(msg: &str) -> ! {
+    // Not recorded as a failure itself, so it is only reported once.
+    panic!(
+        "{}{}: a previous check on this thread panicked and was caught",
+        crate::context::Current,
+        msg
+    )
+}
+
+#[inline(never)]
+#[cold]
+#[track_caller]
+pub(crate) fn expect_none_within_failed(msg: &str, tries: usize, value: &dyn fmt::Debug) -> ! {
+    #[cfg(feature = "std")]
+    crate::hook::on_unexpected_some(value);
+    panic_in_context!("{} after {} tries: {:?}", msg, tries, value)
+}
+
+#[cfg(feature = "serde")]
+#[inline(never)]
+#[cold]
+#[track_caller]
+pub(crate) fn expect_blank_failed(msg: &str, path: &str) -> ! {
+    crate::hook::on_unexpected_some(&DisplayAsDebug(&path));
+    panic_in_context!("{}: `{}` is Some", msg, path)
+}
+
+#[cfg(feature = "serde")]
+#[inline(never)]
+#[cold]
+#[track_caller]
+pub(crate) fn expect_blank_unserializable(msg: &str, path: &str, error: &str) -> ! {
+    panic_in_context!("{}: failed to serialize at `{}`: {}", msg, path, error)
+}
+
+#[inline(never)]
+#[cold]
+#[track_caller]
+pub(crate) fn expect_refcell_none_borrowed(msg: &str) -> ! {
+    panic_in_context!("{}: cannot check the option, it is mutably borrowed", msg)
+}
+
+// Not cold, as collecting unexpected values is the point of the caller.
+#[inline(never)]
+pub(crate) fn write_some(w: &mut dyn fmt::Write, sep: &str, value: &dyn fmt::Debug) -> fmt::Result {
+    w.write_str(sep)?;
+    write!(w, "{:?}", value)
+}
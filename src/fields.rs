@@ -0,0 +1,79 @@
+/// Expects every [`Option`] field of a struct to be [`None`].
+///
+/// The derived implementation checks every field whose type is spelled
+/// `Option<..>` in declaration order and skips the others.
+///
+/// Available with the `derive` feature.
+///
+/// # Examples
+///
+/// ```
+/// use std::panic;
+///
+/// use unwrap_none::ExpectNoneFields;
+///
+/// #[derive(ExpectNoneFields)]
+/// struct Session {
+///     id: u32,
+///     user: Option<String>,
+///     token: Option<u64>,
+/// }
+///
+/// let session = Session { id: 1, user: None, token: None };
+/// assert_eq!(session.id, 1);
+/// session.expect_all_none("session not reset");
+///
+/// let session = Session { id: 2, user: None, token: Some(7) };
+/// # #[cfg(feature = "enforce")] {
+/// let err = panic::catch_unwind(|| session.expect_all_none("session not reset")).unwrap_err();
+/// assert_eq!(
+///     err.downcast_ref::<String>().unwrap(),
+///     "session not reset: field `token` is Some(7)",
+/// );
+/// # }
+/// ```
+///
+/// Tuple structs report the index of the field instead.
+///
+/// ```should_panic
+/// use unwrap_none::ExpectNoneFields;
+///
+/// #[derive(ExpectNoneFields)]
+/// struct Slots(Option<u8>, Option<u8>);
+///
+/// // This will panic with "slots in use: field `1` is Some(3)".
+/// Slots(None, Some(3)).expect_all_none("slots in use");
+/// # #[cfg(not(feature = "enforce"))]
+/// # panic!("the checks are disabled");
+/// ```
+///
+/// Generic structs are supported, with the content of every [`Option`] field
+/// required to implement [`Debug`](core::fmt::Debug). If `unwrap_none` is
+/// re-exported under another path, it can be passed with
+/// `#[unwrap_none(crate = ..)]`.
+///
+/// ```
+/// use unwrap_none as renamed;
+/// use unwrap_none::ExpectNoneFields;
+///
+/// #[derive(ExpectNoneFields)]
+/// #[unwrap_none(crate = renamed)]
+/// struct Cache<K, V> {
+///     key: Option<K>,
+///     value: Option<V>,
+///     hits: usize,
+/// }
+///
+/// let cache = Cache { key: None::<u32>, value: None::<String>, hits: 0 };
+/// assert_eq!(cache.hits, 0);
+/// cache.expect_all_none("cache not cleared");
+/// ```
+pub trait ExpectNoneFields {
+    /// Consumes `self` while expecting every [`Option`] field to be [`None`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if any field is a [`Some`], with a panic message including the
+    /// passed message, the name of the first such field and its content.
+    fn expect_all_none(self, msg: &str);
+}
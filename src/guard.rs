@@ -0,0 +1,217 @@
+use core::fmt;
+use core::ops::{Deref, DerefMut};
+use core::panic::Location;
+
+use crate::failed;
+
+/// A guard expecting a slot to be [`None`] when it is dropped.
+///
+/// This makes sure a value is consumed before the end of a scope, e.g. in a
+/// state machine. The slot can be accessed through the guard with [`Deref`]
+/// and [`DerefMut`].
+///
+/// If the guard is dropped while the thread is already panicking, the slot is
+/// not checked, since panicking again would abort the process.
+///
+/// Available with the `std` feature.
+///
+/// # Panics
+///
+/// Dropping the guard panics if the slot is a [`Some`], with a panic message
+/// including the location the guard was created at, and the content of the
+/// [`Some`].
+///
+/// # Examples
+///
+/// ```
+/// use std::panic;
+///
+/// use unwrap_none::NoneOnDrop;
+///
+/// let mut pending = None;
+/// {
+///     let mut pending = NoneOnDrop::new(&mut pending);
+///     *pending = Some("request");
+///     assert_eq!(pending.take(), Some("request"));
+/// }
+///
+/// # #[cfg(feature = "enforce")] {
+/// let err = panic::catch_unwind(|| {
+///     let mut pending = None;
+///     let mut pending = NoneOnDrop::new(&mut pending);
+///     *pending = Some("request");
+/// })
+/// .unwrap_err();
+/// let msg = err.downcast_ref::<String>().unwrap();
+/// assert!(msg.ends_with("is not None: \"request\""));
+/// # }
+/// ```
+///
+/// The guard does not panic again if the scope is left by a panic:
+///
+/// ```
+/// use std::panic;
+///
+/// use unwrap_none::NoneOnDrop;
+///
+/// let err = panic::catch_unwind(|| {
+///     let mut pending = Some("request");
+///     let _pending = NoneOnDrop::new(&mut pending);
+///     panic!("connection lost");
+/// })
+/// .unwrap_err();
+/// assert_eq!(err.downcast_ref::<&str>(), Some(&"connection lost"));
+/// ```
+pub struct NoneOnDrop<'a, T>
+where
+    T: fmt::Debug,
+{
+    slot: &'a mut Option<T>,
+    location: &'static Location<'static>,
+}
+
+impl<'a, T> NoneOnDrop<'a, T>
+where
+    T: fmt::Debug,
+{
+    /// Guards `slot`, expecting it to be [`None`] when the guard is dropped.
+    #[track_caller]
+    pub fn new(slot: &'a mut Option<T>) -> Self {
+        Self {
+            slot,
+            location: Location::caller(),
+        }
+    }
+}
+
+impl<T> Deref for NoneOnDrop<'_, T>
+where
+    T: fmt::Debug,
+{
+    type Target = Option<T>;
+
+    fn deref(&self) -> &Option<T> {
+        self.slot
+    }
+}
+
+impl<T> DerefMut for NoneOnDrop<'_, T>
+where
+    T: fmt::Debug,
+{
+    fn deref_mut(&mut self) -> &mut Option<T> {
+        self.slot
+    }
+}
+
+impl<T> Drop for NoneOnDrop<'_, T>
+where
+    T: fmt::Debug,
+{
+    fn drop(&mut self) {
+        if std::thread::panicking() {
+            return;
+        }
+        if let Some(val) = failed::enforced(self.slot.as_ref()) {
+            failed::none_on_drop_failed(self.location, val);
+        }
+    }
+}
+
+/// A pending check expecting an [`Option`] to be [`None`], returned by
+/// [`UnwrapNone::check_none`](crate::UnwrapNone::check_none).
+///
+/// The check is performed by [`finish`](MustCheckNone::finish), or when the
+/// value is dropped otherwise. Ignoring it without binding it triggers the
+/// `unused_must_use` lint, but still performs the check immediately.
+///
+/// Like [`NoneOnDrop`], the option is not checked when dropped while the
+/// thread is already panicking.
+///
+/// Available with the `std` feature.
+///
+/// # Panics
+///
+/// Finishing or dropping the check panics if the option is a [`Some`], with a
+/// panic message including the location the check was created at, and the
+/// content of the [`Some`].
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashMap;
+/// use std::panic;
+///
+/// use unwrap_none::UnwrapNone;
+///
+/// let mut map = HashMap::new();
+/// map.insert(1, "one").check_none().finish();
+///
+/// # #[cfg(feature = "enforce")] {
+/// let err = panic::catch_unwind(move || map.insert(1, "uno").check_none().finish())
+///     .unwrap_err();
+/// let msg = err.downcast_ref::<String>().unwrap();
+/// assert!(msg.ends_with("is not None: \"one\""));
+/// # }
+/// ```
+///
+/// The check is also performed if the value is dropped without finishing it:
+///
+/// ```
+/// use std::panic;
+///
+/// use unwrap_none::UnwrapNone;
+///
+/// # #[cfg(feature = "enforce")] {
+/// let err = panic::catch_unwind(|| {
+///     let _check = Some(5).check_none();
+///     // `_check` is dropped here.
+/// })
+/// .unwrap_err();
+/// let msg = err.downcast_ref::<String>().unwrap();
+/// assert!(msg.ends_with("is not None: 5"));
+/// # }
+/// ```
+#[must_use = "the check is performed when this is dropped, call `finish` to perform it now"]
+pub struct MustCheckNone<T>
+where
+    T: fmt::Debug,
+{
+    opt: Option<T>,
+    location: &'static Location<'static>,
+}
+
+impl<T> MustCheckNone<T>
+where
+    T: fmt::Debug,
+{
+    #[track_caller]
+    pub(crate) fn new(opt: Option<T>) -> Self {
+        Self {
+            opt,
+            location: Location::caller(),
+        }
+    }
+
+    /// Performs the check now instead of when dropped.
+    #[track_caller]
+    pub fn finish(mut self) {
+        if let Some(val) = failed::enforced(self.opt.take()) {
+            failed::must_check_none_failed(self.location, &val);
+        }
+    }
+}
+
+impl<T> Drop for MustCheckNone<T>
+where
+    T: fmt::Debug,
+{
+    fn drop(&mut self) {
+        if std::thread::panicking() {
+            return;
+        }
+        if let Some(val) = failed::enforced(self.opt.take()) {
+            failed::must_check_none_failed(self.location, &val);
+        }
+    }
+}
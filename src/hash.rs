@@ -0,0 +1,32 @@
+use core::hash::{Hash, Hasher};
+
+/// A 64-bit FNV-1a hasher, which is small and works without `std`.
+///
+/// It is not resistant against collisions on purpose, so it is only suitable
+/// to tell values apart in panic messages.
+struct Fnv1a(u64);
+
+impl Hasher for Fnv1a {
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 ^= u64::from(byte);
+            self.0 = self.0.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+/// Returns the FNV-1a hash of `value`.
+#[inline(never)]
+#[cold]
+pub(crate) fn short_hash<H>(value: &H) -> u64
+where
+    H: Hash + ?Sized,
+{
+    let mut hasher = Fnv1a(0xcbf2_9ce4_8422_2325);
+    value.hash(&mut hasher);
+    hasher.finish()
+}
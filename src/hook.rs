@@ -0,0 +1,74 @@
+use core::fmt;
+use std::sync::{Arc, PoisonError, RwLock};
+
+type Hook = Arc<dyn Fn(&dyn fmt::Debug) + Send + Sync>;
+
+static HOOK: RwLock<Option<Hook>> = RwLock::new(None);
+
+/// Registers a global hook that is called with the unexpected value whenever
+/// a panicking method of this crate finds one, right before it panics.
+///
+/// This replaces any previously registered hook. Without a hook, the methods
+/// simply panic. Methods that never format the value, such as
+/// [`unwrap_none_redacted`](crate::UnwrapNone::unwrap_none_redacted), do not
+/// call the hook, and neither do checks that fail without a single unexpected
+/// value, such as [`NoneExpectations`](crate::NoneExpectations) finding the
+/// wrong number of [`Some`]s.
+///
+/// Methods that only format a description of the value pass that instead,
+/// e.g. [`unwrap_none_boxed`](crate::UnwrapNoneBoxed::unwrap_none_boxed)
+/// passes the type name, and
+/// [`expect_upgraded_none`](crate::ExpectUpgradedNone::expect_upgraded_none)
+/// the remaining strong count.
+///
+/// The hook is called without holding any lock, so it may register or clear
+/// a hook itself, which takes effect for the next unexpected value.
+///
+/// Available with the `std` feature.
+///
+/// # Examples
+///
+/// ```
+/// use std::panic;
+/// use std::sync::Mutex;
+///
+/// use unwrap_none::UnwrapNone;
+///
+/// static SEEN: Mutex<Vec<String>> = Mutex::new(Vec::new());
+///
+/// unwrap_none::set_on_unexpected_some(|value| {
+///     SEEN.lock().unwrap().push(format!("{:?}", value));
+/// });
+///
+/// None::<i32>.unwrap_none();
+/// assert!(SEEN.lock().unwrap().is_empty());
+///
+/// # #[cfg(feature = "enforce")] {
+/// assert!(panic::catch_unwind(|| Some(42).expect_none_with(|| "slot is in use")).is_err());
+/// assert_eq!(*SEEN.lock().unwrap(), ["42"]);
+///
+/// // The hook may clear itself, e.g. to only see the first value.
+/// unwrap_none::set_on_unexpected_some(|_| unwrap_none::clear_on_unexpected_some());
+/// assert!(panic::catch_unwind(|| Some(1).unwrap_none()).is_err());
+/// # }
+/// ```
+pub fn set_on_unexpected_some<F>(f: F)
+where
+    F: Fn(&dyn fmt::Debug) + Send + Sync + 'static,
+{
+    *HOOK.write().unwrap_or_else(PoisonError::into_inner) = Some(Arc::new(f));
+}
+
+/// Removes the hook registered with [`set_on_unexpected_some`], if any.
+///
+/// Available with the `std` feature.
+pub fn clear_on_unexpected_some() {
+    *HOOK.write().unwrap_or_else(PoisonError::into_inner) = None;
+}
+
+pub(crate) fn on_unexpected_some(value: &dyn fmt::Debug) {
+    let hook = HOOK.read().unwrap_or_else(PoisonError::into_inner).clone();
+    if let Some(hook) = hook {
+        hook(value);
+    }
+}
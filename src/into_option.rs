@@ -0,0 +1,68 @@
+/// Converts a value with an obvious "empty" state into an [`Option`].
+///
+/// This is accepted by the free functions [`expect_none`](crate::expect_none())
+/// and [`unwrap_none`](crate::unwrap_none()), so types that are [`None`]-like
+/// can be checked without converting them by hand first. The conversion must
+/// be cheap and must not fail.
+///
+/// # Examples
+///
+/// ```
+/// use std::panic;
+///
+/// use unwrap_none::IntoOption;
+///
+/// #[derive(Debug)]
+/// enum Lease {
+///     Free,
+///     Held { owner: u32 },
+/// }
+///
+/// impl IntoOption<u32> for Lease {
+///     fn into_option(self) -> Option<u32> {
+///         match self {
+///             Lease::Free => None,
+///             Lease::Held { owner } => Some(owner),
+///         }
+///     }
+/// }
+///
+/// unwrap_none::expect_none(Lease::Free, "lease is held");
+///
+/// # #[cfg(feature = "enforce")] {
+/// let err = panic::catch_unwind(|| {
+///     unwrap_none::expect_none(Lease::Held { owner: 3 }, "lease is held")
+/// })
+/// .unwrap_err();
+/// # #[cfg(not(feature = "minimal-panic"))]
+/// assert_eq!(err.downcast_ref::<String>().unwrap(), "lease is held: 3");
+/// # }
+/// ```
+pub trait IntoOption<T> {
+    /// Converts `self` into an [`Option`], which is [`None`] if `self` is
+    /// empty.
+    fn into_option(self) -> Option<T>;
+}
+
+impl<T> IntoOption<T> for Option<T> {
+    #[inline]
+    fn into_option(self) -> Option<T> {
+        self
+    }
+}
+
+/// Converts [`Ok`] into [`Some`] and [`Err`] into [`None`], as the error
+/// carries no information.
+///
+/// # Examples
+///
+/// ```
+/// let pending: Result<u32, ()> = Err(());
+/// unwrap_none::unwrap_none(pending);
+/// ```
+impl<T> IntoOption<T> for Result<T, ()> {
+    #[inline]
+    fn into_option(self) -> Option<T> {
+        self.ok()
+    }
+}
@@ -0,0 +1,43 @@
+use core::fmt::{self, Write};
+use core::panic::Location;
+use std::io::{self, Write as _};
+use std::string::String;
+
+/// Prints a JSON line describing the unexpected value to stdout.
+#[inline(never)]
+#[cold]
+pub(crate) fn emit_unexpected(value: &dyn fmt::Debug, location: &Location<'_>) {
+    let mut line = String::from(r#"{"event":"unexpected_some","value":""#);
+    let _ = write!(Escaped(&mut line), "{:?}", value);
+    line.push_str(r#"","location":""#);
+    let _ = write!(
+        Escaped(&mut line),
+        "{}:{}",
+        location.file(),
+        location.line()
+    );
+    line.push_str("\"}\n");
+    // Like the other non-panicking methods, never panic, not even if stdout is
+    // closed.
+    let _ = io::stdout().lock().write_all(line.as_bytes());
+}
+
+/// Escapes everything written to it for use in a JSON string.
+struct Escaped<'a>(&'a mut String);
+
+impl Write for Escaped<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.chars() {
+            match c {
+                '"' => self.0.push_str("\\\""),
+                '\\' => self.0.push_str("\\\\"),
+                '\n' => self.0.push_str("\\n"),
+                '\r' => self.0.push_str("\\r"),
+                '\t' => self.0.push_str("\\t"),
+                c if c < ' ' => write!(self.0, "\\u{:04x}", c as u32)?,
+                c => self.0.push(c),
+            }
+        }
+        Ok(())
+    }
+}
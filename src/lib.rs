@@ -3,10 +3,281 @@
 #![no_std]
 
 //! See docs with examples on [`UnwrapNone#required-methods`].
+//!
+//! # Disabling the checks
+//!
+//! The checks can be turned off for a whole build by disabling the default
+//! `enforce` feature, without touching any call sites. Every method, function
+//! and macro that would panic or abort on an unexpected value then does
+//! nothing, apart from still consuming it. Unlike
+//! [`debug_unwrap_none`](UnwrapNone::debug_unwrap_none) this does not depend
+//! on the build profile.
+//!
+//! Methods that don't panic, such as
+//! [`try_unwrap_none`](UnwrapNone::try_unwrap_none), are not affected. Neither
+//! is [`unwrap_none_unchecked`](UnwrapNone::unwrap_none_unchecked), since
+//! it is not a check but a promise made by the caller.
+//!
+//! ```
+//! use std::panic;
+//!
+//! use unwrap_none::{assert_none, UnwrapEmpty, UnwrapNone};
+//!
+//! let enforced = cfg!(feature = "enforce");
+//! assert_eq!(panic::catch_unwind(|| Some(5).unwrap_none()).is_err(), enforced);
+//! assert_eq!(panic::catch_unwind(|| [1, 2].unwrap_empty()).is_err(), enforced);
+//! assert_eq!(panic::catch_unwind(|| assert_none!(Some(5))).is_err(), enforced);
+//! // This never panics.
+//! assert_eq!(Some(5).try_unwrap_none(), Err(5));
+//! ```
 
-use core::fmt;
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
 
-pub trait UnwrapNone<T> {
+use core::{fmt, hint, ops::ControlFlow};
+
+mod all;
+mod batch;
+#[cfg(feature = "serde")]
+mod blank;
+mod borrowed;
+#[cfg(feature = "alloc")]
+mod boxed;
+#[cfg(feature = "sentry")]
+mod capture;
+mod cell;
+mod check;
+#[cfg(feature = "claim")]
+pub mod claim;
+#[cfg(feature = "std")]
+mod context;
+#[cfg(feature = "miette")]
+mod diagnostic;
+mod empty;
+#[cfg(feature = "std")]
+mod error;
+#[cfg(feature = "alloc")]
+mod expectations;
+mod expected;
+mod failed;
+#[cfg(feature = "derive")]
+mod fields;
+#[cfg(feature = "std")]
+mod guard;
+mod hash;
+#[cfg(feature = "std")]
+mod hook;
+mod into_option;
+#[cfg(feature = "json-log")]
+mod json;
+#[cfg(feature = "log")]
+mod logging;
+#[doc(hidden)]
+pub mod macros;
+mod maybe_debug;
+#[cfg(feature = "metrics")]
+mod metrics;
+#[cfg(feature = "std")]
+mod mode;
+mod nested;
+#[cfg(feature = "std")]
+mod once;
+mod poll;
+mod result;
+mod sealed;
+mod silent;
+#[cfg(feature = "std")]
+mod strict;
+mod styled;
+#[cfg(feature = "timing")]
+mod timing;
+#[cfg(feature = "tracing")]
+mod trace;
+#[cfg(feature = "std")]
+mod translate;
+mod truncate;
+#[cfg(feature = "ufmt")]
+mod ufmt_panic;
+#[cfg(feature = "std")]
+mod unexpected;
+mod unset;
+#[cfg(feature = "alloc")]
+mod upgraded;
+mod witness;
+
+pub use all::{expect_none_loop, expect_none_within, unwrap_none_all, UnwrapNoneAll};
+pub use batch::NoneBatch;
+#[cfg(feature = "serde")]
+pub use blank::{expect_blank, first_some_path};
+pub use borrowed::UnwrapNoneRef;
+#[cfg(feature = "alloc")]
+pub use boxed::UnwrapNoneBoxed;
+pub use cell::{expect_cell_none, expect_refcell_none};
+pub use check::NoneCheck;
+#[cfg(feature = "std")]
+pub use context::{push_none_context, NoneContextGuard};
+#[cfg(feature = "miette")]
+pub use diagnostic::UnwrapNoneDiagnostic;
+pub use empty::{ExpectEmptyIter, UnwrapEmpty};
+#[cfg(feature = "std")]
+pub use error::UnwrapNoneError;
+#[cfg(feature = "alloc")]
+pub use expectations::NoneExpectations;
+pub use expected::{AssertEmpty, ExpectedNone};
+#[cfg(feature = "derive")]
+pub use fields::ExpectNoneFields;
+#[cfg(feature = "std")]
+pub use guard::{MustCheckNone, NoneOnDrop};
+#[cfg(feature = "std")]
+pub use hook::{clear_on_unexpected_some, set_on_unexpected_some};
+pub use into_option::IntoOption;
+pub use maybe_debug::MaybeDebug;
+#[cfg(feature = "metrics")]
+pub use metrics::unexpected_some_count;
+#[cfg(feature = "std")]
+pub use mode::{panic_mode, set_panic_mode, PanicMode};
+pub use nested::ExpectFlatNone;
+pub use poll::ExpectPending;
+pub use result::{ExpectNoneInner, ExpectOkUnit};
+pub use silent::UnwrapNoneSilent;
+pub use styled::FormatFlags;
+#[cfg(feature = "timing")]
+pub use timing::{timed_stats, TimedStats};
+#[cfg(feature = "std")]
+pub use translate::set_message_translator;
+#[cfg(feature = "std")]
+pub use unexpected::UnexpectedSome;
+pub use unset::ExpectUnset;
+#[cfg(feature = "derive")]
+pub use unwrap_none_derive::ExpectNoneFields;
+#[cfg(feature = "alloc")]
+pub use upgraded::ExpectUpgradedNone;
+pub use witness::IsNone;
+
+/// The prefix of the panic message of [`UnwrapNone::unwrap_none_machine`].
+///
+/// The full message is this prefix followed by `Some({:?})`. This format is
+/// stable, so tools scraping logs can rely on it to detect these failures.
+///
+/// The messages of [`UnwrapNone::expect_none`] and [`UnwrapNone::unwrap_none`]
+/// are stable as well and have the form `{msg}: {:?}`, where `msg` is the
+/// passed message or ``called `Option::unwrap_none()` on a `Some` value``
+/// respectively.
+pub const EXPECT_NONE_PREFIX: &str = "unwrap_none: ";
+
+/// Expects an [`Option`] to be [`None`].
+///
+/// This is implemented for every `Option<T>`, including borrowed options such
+/// as `Option<&T>` returned by [`Option::as_ref`] or [`Iterator::next`] on a
+/// borrowing iterator. Only the outer option is checked, i.e. `Some(&None)`
+/// is still a [`Some`].
+///
+/// Mutably borrowed options such as `Option<&mut T>` returned by
+/// [`HashMap::get_mut`] are supported as well. Since the methods consume the
+/// option, the mutable borrow ends with the call and the borrowed collection
+/// can be used again right after it.
+///
+/// [`HashMap::get_mut`]: https://doc.rust-lang.org/std/collections/struct.HashMap.html#method.get_mut
+///
+/// Options behind a smart pointer need to be dereferenced or borrowed first.
+/// Borrows of options, i.e. `&Option<T>` and `&mut Option<T>`, are supported
+/// by [`UnwrapNoneRef`] instead, since this trait consumes the option.
+///
+/// Failures are handled out of line, so on success the checks cost no more
+/// than [`Option::is_some`]. For niche-optimized options such as
+/// `Option<NonZeroU32>` or `Option<&T>` that is a single comparison against
+/// zero. CI disassembles a `fn(Option<NonZeroU32>)` calling
+/// [`unwrap_none`](UnwrapNone::unwrap_none) in `examples/symbols.rs` and
+/// checks that its happy path is `test`, a branch and `ret` on x86-64.
+///
+/// # Examples
+///
+/// ```
+/// use unwrap_none::UnwrapNone;
+///
+/// let slot: Option<String> = None;
+/// slot.as_ref().expect_none("slot is in use");
+///
+/// let boxed: Box<Option<u32>> = Box::new(None);
+/// boxed.as_ref().as_ref().unwrap_none();
+/// (*boxed).unwrap_none();
+/// ```
+///
+/// ```
+/// use std::panic;
+///
+/// use unwrap_none::UnwrapNone;
+///
+/// let slot = Some(String::from("in use"));
+/// # #[cfg(feature = "enforce")] {
+/// let err = panic::catch_unwind(|| slot.as_ref().expect_none("slot is in use")).unwrap_err();
+/// let msg = err.downcast_ref::<String>().unwrap();
+/// # #[cfg(not(feature = "minimal-panic"))]
+/// assert_eq!(msg, "slot is in use: \"in use\"");
+///
+/// // The inner option is not inspected.
+/// let nested: Option<Option<u32>> = Some(None);
+/// assert!(panic::catch_unwind(|| nested.as_ref().unwrap_none()).is_err());
+/// # }
+/// ```
+///
+/// ```
+/// use std::collections::HashMap;
+/// use std::panic;
+///
+/// use unwrap_none::UnwrapNone;
+///
+/// let mut registry = HashMap::new();
+/// registry.get_mut("alice").expect_none("user is registered");
+/// // The mutable borrow taken by `get_mut` has ended already.
+/// registry.insert("alice", 1);
+///
+/// # #[cfg(feature = "enforce")] {
+/// let err = panic::catch_unwind(move || {
+///     registry.get_mut("alice").expect_none("user is registered")
+/// })
+/// .unwrap_err();
+/// let msg = err.downcast_ref::<String>().unwrap();
+/// # #[cfg(not(feature = "minimal-panic"))]
+/// assert_eq!(msg, "user is registered: 1");
+/// # }
+/// ```
+///
+/// ```
+/// use std::mem;
+/// use std::num::NonZeroU32;
+/// use std::panic;
+///
+/// use unwrap_none::UnwrapNone;
+///
+/// // `None` is represented by zero, so checking it needs no discriminant.
+/// assert_eq!(mem::size_of::<Option<NonZeroU32>>(), mem::size_of::<u32>());
+/// NonZeroU32::new(0).unwrap_none();
+///
+/// # #[cfg(feature = "enforce")] {
+/// let err = panic::catch_unwind(|| NonZeroU32::new(7).unwrap_none()).unwrap_err();
+/// let msg = err.downcast_ref::<String>().unwrap();
+/// # #[cfg(not(feature = "minimal-panic"))]
+/// assert!(msg.ends_with(": 7"));
+/// # }
+/// ```
+///
+/// This trait is sealed and only implemented for [`Option`], so methods can be
+/// added to it without breaking downstream crates:
+///
+/// ```compile_fail,E0277
+/// use unwrap_none::UnwrapNone;
+///
+/// struct Slot;
+///
+/// impl UnwrapNone<u32> for Slot {
+///     fn expect_none(self, _msg: &str) {}
+///     fn unwrap_none(self) {}
+/// }
+/// ```
+pub trait UnwrapNone<T>: sealed::Sealed {
     /// Consumes `self` while expecting [`None`] and returning nothing.
     ///
     /// # Panics
@@ -14,6 +285,10 @@ pub trait UnwrapNone<T> {
     /// Panics if the value is a [`Some`], with a panic message including the
     /// passed message, and the content of the [`Some`].
     ///
+    /// With the `minimal-panic` feature the content is left out, see
+    /// [`MaybeDebug`]. With the `std` feature the message is first passed
+    /// through the translator registered with `set_message_translator`.
+    ///
     /// # Examples
     ///
     /// ```
@@ -39,8 +314,12 @@ pub trait UnwrapNone<T> {
     ///     // insert the same `i * i` key, returning the old `Some(i)`.
     ///     sqrts.insert(i * i, i).expect_none("duplicate key");
     /// }
+    /// # #[cfg(not(feature = "enforce"))]
+    /// # panic!("the checks are disabled");
     /// ```
-    fn expect_none(self, msg: &str);
+    fn expect_none(self, msg: &str)
+    where
+        T: MaybeDebug;
 
     /// Consumes `self` while expecting [`None`] and returning nothing.
     ///
@@ -49,6 +328,42 @@ pub trait UnwrapNone<T> {
     /// Panics if the value is a [`Some`], with a custom panic message provided
     /// by the [`Some`]'s value.
     ///
+    /// With the `minimal-panic` feature the content is left out, see
+    /// [`MaybeDebug`].
+    ///
+    /// # Performance
+    ///
+    /// Only the check for [`None`] is inlined, the panic is kept out of line
+    /// and marked as cold. Like a hand-written `if let Some(v) = opt {
+    /// panic!(..) }`, a loop calling this on a slice of options compiles to a
+    /// single compare and branch per option. The `none` group of the benchmark
+    /// in `benches/` compares both, so there is no faster variant to offer.
+    ///
+    /// None of the formatting machinery is part of the happy path, as the
+    /// `fmt::Arguments` for the panic are only built in the cold function. On
+    /// x86-64, the happy path of a standalone `fn(Option<u64>)` calling this
+    /// is 3 instructions without any calls: a compare, a branch and `ret`. CI
+    /// checks this by disassembling `check_u64` in `examples/symbols.rs`.
+    ///
+    /// The [`Debug`](fmt::Debug) implementation of `T` is only touched for a
+    /// [`Some`], so checking a [`None`] never allocates, even if formatting a
+    /// `T` would:
+    ///
+    /// ```
+    /// use assert_no_alloc::{assert_no_alloc, AllocDisabler};
+    /// use unwrap_none::UnwrapNone;
+    ///
+    /// #[global_allocator]
+    /// static ALLOCATOR: AllocDisabler = AllocDisabler;
+    ///
+    /// let buffers: Vec<Option<Vec<f32>>> = vec![None; 64];
+    /// assert_no_alloc(|| {
+    ///     for buffer in &buffers {
+    ///         buffer.as_ref().unwrap_none();
+    ///     }
+    /// });
+    /// ```
+    ///
     /// [`Some(v)`]: Some
     ///
     /// # Examples
@@ -76,8 +391,57 @@ pub trait UnwrapNone<T> {
     ///     // insert the same `i * i` key, returning the old `Some(i)`.
     ///     sqrts.insert(i * i, i).unwrap_none();
     /// }
+    /// # #[cfg(not(feature = "enforce"))]
+    /// # panic!("the checks are disabled");
+    /// ```
+    fn unwrap_none(self)
+    where
+        T: MaybeDebug;
+
+    /// Consumes `self` while expecting [`None`] and returning nothing, without
+    /// tracking the caller location.
+    ///
+    /// This is [`unwrap_none`](UnwrapNone::unwrap_none) without
+    /// `#[track_caller]`, so the panic message points into this crate instead
+    /// of at the caller. The happy path is the same either way, as the caller
+    /// location is only passed along when panicking, and the `track_caller`
+    /// group of the benchmark in `benches/` measures no difference between
+    /// the two. Passing the location does take an extra instruction on the
+    /// cold path of every call site, so prefer this only where code size
+    /// matters more than knowing which call failed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is a [`Some`], with a custom panic message provided
+    /// by the [`Some`]'s value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::panic;
+    /// use std::sync::atomic::{AtomicU32, Ordering};
+    ///
+    /// use unwrap_none::UnwrapNone;
+    ///
+    /// None::<u8>.unwrap_none_notrack();
+    ///
+    /// static LINE: AtomicU32 = AtomicU32::new(0);
+    /// panic::set_hook(Box::new(|info| {
+    ///     LINE.store(info.location().unwrap().line(), Ordering::Relaxed);
+    /// }));
+    /// let line = line!() + 1;
+    /// # #[cfg(feature = "enforce")] {
+    /// let err = panic::catch_unwind(|| Some(3).unwrap_none_notrack()).unwrap_err();
+    /// let _ = panic::take_hook();
+    /// assert_ne!(LINE.load(Ordering::Relaxed), line);
+    /// let msg = err.downcast_ref::<String>().unwrap();
+    /// # #[cfg(not(feature = "minimal-panic"))]
+    /// assert_eq!(msg, "called `Option::unwrap_none()` on a `Some` value: 3");
+    /// # }
     /// ```
-    fn unwrap_none(self);
+    fn unwrap_none_notrack(self)
+    where
+        T: MaybeDebug;
 
     /// Calls the supplied closure only if the instance is `None`.
     ///
@@ -104,43 +468,3715 @@ pub trait UnwrapNone<T> {
     fn unwrap_none_or_else<F>(self, f: F)
     where
         F: FnOnce(T);
-}
 
-impl<T> UnwrapNone<T> for Option<T>
-where
-    T: fmt::Debug,
-{
-    #[inline]
-    #[track_caller]
-    fn expect_none(self, msg: &str) {
-        if let Some(val) = self {
-            expect_none_failed(msg, &val);
-        }
-    }
+    /// Returns `Ok(())` if the instance is [`None`], and calls the supplied
+    /// fallible closure with the content of a [`Some`] otherwise, returning its
+    /// result.
+    ///
+    /// This is [`unwrap_none_or_else`](UnwrapNone::unwrap_none_or_else) for
+    /// handlers that can fail themselves, so they compose with `?`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    ///
+    /// use unwrap_none::UnwrapNone;
+    ///
+    /// fn register(names: &mut HashMap<u32, String>, id: u32, name: &str) -> Result<(), String> {
+    ///     names.insert(id, name.to_owned()).unwrap_none_or_try(|old| {
+    ///         // Registering the same name again is fine.
+    ///         if old == name {
+    ///             Ok(())
+    ///         } else {
+    ///             Err(format!("{} is already registered as {}", id, old))
+    ///         }
+    ///     })
+    /// }
+    ///
+    /// let mut names = HashMap::new();
+    /// assert_eq!(register(&mut names, 1, "alice"), Ok(()));
+    /// assert_eq!(register(&mut names, 1, "alice"), Ok(()));
+    /// assert_eq!(
+    ///     register(&mut names, 1, "bob"),
+    ///     Err("1 is already registered as alice".to_owned()),
+    /// );
+    /// ```
+    fn unwrap_none_or_try<E, F>(self, f: F) -> Result<(), E>
+    where
+        F: FnOnce(T) -> Result<(), E>;
 
-    #[inline]
-    #[track_caller]
-    fn unwrap_none(self) {
-        if let Some(val) = self {
-            expect_none_failed("called `Option::unwrap_none()` on a `Some` value", &val);
-        }
-    }
+    /// Calls the supplied closure only if the instance is a [`Some`], with
+    /// its content.
+    ///
+    /// This is like [`unwrap_none_or_else`](UnwrapNone::unwrap_none_or_else),
+    /// except the closure is borrowed, so the same closure can be reused for
+    /// many options, e.g. to collect all unexpected values in a loop.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    ///
+    /// use unwrap_none::UnwrapNone;
+    ///
+    /// let mut map = HashMap::new();
+    /// let mut replaced = Vec::new();
+    /// let mut collect = |val| replaced.push(val);
+    /// for (k, v) in [(1, 'a'), (2, 'b'), (1, 'c'), (2, 'd'), (3, 'e')] {
+    ///     map.insert(k, v).for_some(&mut collect);
+    /// }
+    /// assert_eq!(replaced, ['a', 'b']);
+    /// ```
+    fn for_some<F>(self, f: &mut F)
+    where
+        F: FnMut(T);
 
-    #[inline]
-    fn unwrap_none_or_else<F>(self, f: F)
+    /// Consumes `self`, returning an iterator over the unexpected value, if
+    /// any.
+    ///
+    /// This is the same as [`Option::into_iter`], but makes it clear the
+    /// yielded values are anomalies. It is useful to gather all unexpected
+    /// values of many options with e.g. [`Iterator::flat_map`] instead of
+    /// panicking on the first.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    ///
+    /// use unwrap_none::UnwrapNone;
+    ///
+    /// let mut map = HashMap::new();
+    /// let replaced: Vec<_> = [(1, 'a'), (2, 'b'), (1, 'c'), (3, 'd'), (2, 'e')]
+    ///     .into_iter()
+    ///     .flat_map(|(k, v)| map.insert(k, v).into_unexpected())
+    ///     .collect();
+    /// assert_eq!(replaced, ['a', 'b']);
+    /// ```
+    fn into_unexpected(self) -> core::option::IntoIter<T>;
+
+    /// Consumes `self`, pushing the unexpected value, if any, into `sink`.
+    ///
+    /// This never panics, so all unexpected values of e.g. a bulk import can
+    /// be collected and reported at once instead of failing on the first.
+    ///
+    /// Available with the `alloc` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    ///
+    /// use unwrap_none::UnwrapNone;
+    ///
+    /// let mut map = HashMap::new();
+    /// let mut conflicts = Vec::new();
+    /// for (k, v) in [(1, 'a'), (2, 'b'), (1, 'c'), (3, 'd'), (2, 'e'), (1, 'f')] {
+    ///     map.insert(k, v).collect_some_into(&mut conflicts);
+    /// }
+    /// assert_eq!(conflicts, ['a', 'b', 'c']);
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn collect_some_into(self, sink: &mut alloc::vec::Vec<T>);
+
+    /// Consumes `self`, writing the unexpected value, if any, into `w`,
+    /// preceded by `sep`.
+    ///
+    /// This is `collect_some_into` of the `alloc` feature without an
+    /// allocator: since the value is written with its
+    /// [`Debug`](fmt::Debug) implementation, all unexpected values of many
+    /// calls can be collected into e.g. a fixed-size buffer for a single
+    /// combined report. Nothing is written if the value is [`None`].
+    ///
+    /// This never panics.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing into `w` fails, e.g. because it is full.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use unwrap_none::UnwrapNone;
+    ///
+    /// let mut report = heapless::String::<32>::new();
+    /// for opt in [Some(1), None, Some(2), Some(3)] {
+    ///     opt.write_some_into(&mut report, ", ").unwrap();
+    /// }
+    /// assert_eq!(report, ", 1, 2, 3");
+    ///
+    /// let mut report = heapless::String::<4>::new();
+    /// assert!(Some("too long").write_some_into(&mut report, "").is_err());
+    /// ```
+    fn write_some_into<W>(self, w: &mut W, sep: &str) -> fmt::Result
     where
-        F: FnOnce(T),
-    {
-        if let Some(val) = self {
-            f(val)
-        }
-    }
-}
+        T: fmt::Debug,
+        W: fmt::Write;
 
-// This is a separate function to reduce the code size of .expect_none() itself.
-#[inline(never)]
-#[cold]
-#[track_caller]
-fn expect_none_failed(msg: &str, value: &dyn fmt::Debug) -> ! {
-    panic!("{}: {:?}", msg, value)
+    /// Consumes `self` while expecting [`None`], returning the contained
+    /// value as an error otherwise.
+    ///
+    /// Unlike [`unwrap_none`](UnwrapNone::unwrap_none) this never panics and
+    /// does not require `T` to implement [`Debug`](fmt::Debug). The decision
+    /// to panic can be deferred by passing the result on and calling
+    /// [`ExpectOkUnit::expect_ok`] on it later.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    ///
+    /// use unwrap_none::UnwrapNone;
+    ///
+    /// fn insert_all(map: &mut HashMap<i32, i32>, values: &[i32]) -> Result<(), i32> {
+    ///     for &i in values {
+    ///         map.insert(i * i, i).try_unwrap_none()?;
+    ///     }
+    ///     Ok(())
+    /// }
+    ///
+    /// let mut map = HashMap::new();
+    /// assert_eq!(insert_all(&mut map, &[1, 2, 3]), Ok(()));
+    ///
+    /// // `-2 * -2` collides with the `2 * 2` key inserted above.
+    /// let mut map = HashMap::new();
+    /// assert_eq!(insert_all(&mut map, &[1, 2, -2]), Err(2));
+    /// ```
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use std::panic;
+    ///
+    /// use unwrap_none::{ExpectOkUnit, UnwrapNone};
+    ///
+    /// let mut map = HashMap::new();
+    /// let first = map.insert(1, "one").try_unwrap_none();
+    /// let second = map.insert(1, "uno").try_unwrap_none();
+    ///
+    /// // Only panic at the edge.
+    /// first.expect_ok("duplicate key");
+    /// # #[cfg(feature = "enforce")] {
+    /// let err = panic::catch_unwind(|| second.expect_ok("duplicate key")).unwrap_err();
+    /// assert_eq!(err.downcast_ref::<String>().unwrap(), "duplicate key: \"one\"");
+    /// # }
+    /// ```
+    fn try_unwrap_none(self) -> Result<(), T>;
+
+    /// Consumes `self` while expecting [`None`], returning the contained
+    /// value as an error otherwise.
+    ///
+    /// This is the same as [`try_unwrap_none`](UnwrapNone::try_unwrap_none),
+    /// named for use with the `?` operator. The unexpected value is the
+    /// residual, so `?` propagates it as is, or converted with [`From`] into
+    /// the error type of the function.
+    ///
+    /// # Examples
+    ///
+    /// Propagating into a [`Result`]:
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    ///
+    /// use unwrap_none::UnwrapNone;
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// struct Duplicate(&'static str);
+    ///
+    /// impl From<&'static str> for Duplicate {
+    ///     fn from(old: &'static str) -> Self {
+    ///         Self(old)
+    ///     }
+    /// }
+    ///
+    /// fn register(map: &mut HashMap<u32, &'static str>) -> Result<(), Duplicate> {
+    ///     map.insert(1, "first").none_try()?;
+    ///     map.insert(1, "second").none_try()?;
+    ///     Ok(())
+    /// }
+    ///
+    /// assert_eq!(register(&mut HashMap::new()), Err(Duplicate("first")));
+    /// ```
+    ///
+    /// Propagating into an [`Option`], which discards the unexpected value:
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    ///
+    /// use unwrap_none::UnwrapNone;
+    ///
+    /// fn register(map: &mut HashMap<u32, &'static str>, id: u32) -> Option<()> {
+    ///     map.insert(id, "user").none_try().ok()?;
+    ///     Some(())
+    /// }
+    ///
+    /// let mut map = HashMap::new();
+    /// assert_eq!(register(&mut map, 1), Some(()));
+    /// assert_eq!(register(&mut map, 1), None);
+    /// ```
+    fn none_try(self) -> Result<(), T>;
+
+    /// Consumes `self`, returning whether it is [`None`] along with the
+    /// unexpected value, if any.
+    ///
+    /// This never panics and does not require `T` to implement
+    /// [`Debug`](fmt::Debug), which suits fuzz targets and property tests where
+    /// a panic would abort the run.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    ///
+    /// use unwrap_none::UnwrapNone;
+    ///
+    /// assert_eq!(None::<u32>.is_none_checked(), (true, None));
+    /// assert_eq!(Some(3).is_none_checked(), (false, Some(3)));
+    ///
+    /// // A property: inserting distinct keys never replaces a value.
+    /// let prop = |keys: &[u8]| {
+    ///     let mut map = HashMap::new();
+    ///     keys.iter().all(|&k| map.insert(k, ()).is_none_checked().0)
+    /// };
+    /// for case in [&[][..], &[1], &[1, 2, 3], &[255, 0, 7]] {
+    ///     assert!(prop(case));
+    /// }
+    /// assert!(!prop(&[4, 4]));
+    /// ```
+    // Consuming `self` is intended, the value is handed back.
+    #[allow(clippy::wrong_self_convention)]
+    fn is_none_checked(self) -> (bool, Option<T>);
+
+    /// Transforms `self` into an `Option<()>`, mapping [`None`] to `Some(())`
+    /// and [`Some`] to [`None`].
+    ///
+    /// This allows returning early with `?` from functions returning an
+    /// [`Option`]. The content of the [`Some`] is dropped, use
+    /// [`try_unwrap_none`](UnwrapNone::try_unwrap_none) in functions returning
+    /// a [`Result`] to keep it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    ///
+    /// use unwrap_none::UnwrapNone;
+    ///
+    /// fn insert_all(map: &mut HashMap<i32, i32>, values: &[i32]) -> Option<()> {
+    ///     for &i in values {
+    ///         map.insert(i * i, i).none_or_err()?;
+    ///     }
+    ///     Some(())
+    /// }
+    ///
+    /// let mut map = HashMap::new();
+    /// assert_eq!(insert_all(&mut map, &[1, 2, 3]), Some(()));
+    ///
+    /// let mut map = HashMap::new();
+    /// assert_eq!(insert_all(&mut map, &[1, 2, -2, 4]), None);
+    /// assert!(!map.contains_key(&16));
+    /// ```
+    fn none_or_err(self) -> Option<()>;
+
+    /// Consumes `self` while expecting [`None`] and returning nothing.
+    ///
+    /// This behaves like [`expect_none`](UnwrapNone::expect_none), except the
+    /// message is only built by calling `f` if the value is a [`Some`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is a [`Some`], with a panic message including the
+    /// message returned by `f`, and the content of the [`Some`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    ///
+    /// use unwrap_none::UnwrapNone;
+    ///
+    /// let mut squares = HashMap::new();
+    /// for i in -10..=10 {
+    ///     // The closure is never called, since all keys are unique.
+    ///     squares
+    ///         .insert(i, i * i)
+    ///         .expect_none_with(|| -> String { unreachable!() });
+    /// }
+    /// ```
+    ///
+    /// ```should_panic
+    /// use std::collections::HashMap;
+    ///
+    /// use unwrap_none::UnwrapNone;
+    ///
+    /// let mut sqrts = HashMap::new();
+    /// for i in -10..=10 {
+    ///     // This will panic, since both negative and positive `i` will
+    ///     // insert the same `i * i` key, returning the old `Some(i)`.
+    ///     sqrts
+    ///         .insert(i * i, i)
+    ///         .expect_none_with(|| format!("duplicate key {}", i * i));
+    /// }
+    /// # #[cfg(not(feature = "enforce"))]
+    /// # panic!("the checks are disabled");
+    /// ```
+    fn expect_none_with<F, D>(self, f: F)
+    where
+        T: fmt::Debug,
+        F: FnOnce() -> D,
+        D: fmt::Display;
+
+    /// Consumes `self` while expecting [`None`] and returning nothing,
+    /// building the panic message from the unexpected value.
+    ///
+    /// Unlike [`expect_none_with`](UnwrapNone::expect_none_with), the closure
+    /// gets a reference to the content of the [`Some`], and the content is not
+    /// appended to the message. This allows messages tailored to the value,
+    /// and does not require `T` to implement [`Debug`](fmt::Debug). The
+    /// closure is only called if the value is a [`Some`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is a [`Some`], with the message returned by `f` as
+    /// panic message.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::panic;
+    ///
+    /// use unwrap_none::UnwrapNone;
+    ///
+    /// struct User {
+    ///     id: u32,
+    /// }
+    ///
+    /// None::<User>.expect_none_map(|user| format!("conflicting id {}", user.id));
+    ///
+    /// # #[cfg(feature = "enforce")] {
+    /// let err = panic::catch_unwind(|| {
+    ///     Some(User { id: 42 })
+    ///         .expect_none_map(|user| format!("conflicting id {} already present", user.id))
+    /// })
+    /// .unwrap_err();
+    /// let msg = err.downcast_ref::<String>().unwrap();
+    /// assert_eq!(msg, "conflicting id 42 already present");
+    /// # }
+    /// ```
+    fn expect_none_map<M, F>(self, f: F)
+    where
+        M: fmt::Display,
+        F: FnOnce(&T) -> M;
+
+    /// Consumes `self` while expecting [`None`] and returning nothing.
+    ///
+    /// This behaves like [`expect_none`](UnwrapNone::expect_none), except the
+    /// message is passed as [`fmt::Arguments`], which is only formatted if
+    /// the value is a [`Some`]. Unlike
+    /// [`expect_none_with`](UnwrapNone::expect_none_with) this doesn't need a
+    /// closure.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is a [`Some`], with a panic message including the
+    /// formatted message, and the content of the [`Some`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    ///
+    /// use unwrap_none::UnwrapNone;
+    ///
+    /// let mut squares = HashMap::new();
+    /// for i in -10..=10 {
+    ///     // This will not panic, since all keys are unique.
+    ///     squares
+    ///         .insert(i, i * i)
+    ///         .expect_none_fmt(format_args!("duplicate key {}", i));
+    /// }
+    /// ```
+    ///
+    /// ```
+    /// use std::panic;
+    ///
+    /// use unwrap_none::UnwrapNone;
+    ///
+    /// let key = 4;
+    /// # #[cfg(feature = "enforce")] {
+    /// let err = panic::catch_unwind(|| Some(2).expect_none_fmt(format_args!("duplicate key {key}")))
+    ///     .unwrap_err();
+    /// assert_eq!(err.downcast_ref::<String>().unwrap(), "duplicate key 4: 2");
+    /// # }
+    /// ```
+    fn expect_none_fmt(self, args: fmt::Arguments<'_>)
+    where
+        T: fmt::Debug;
+
+    /// Consumes `self` while expecting [`None`] and returning nothing.
+    ///
+    /// This behaves like [`expect_none`](UnwrapNone::expect_none), except the
+    /// message can be anything that implements [`Display`](fmt::Display),
+    /// such as a [`String`] or an error type. It is only formatted if the value
+    /// is a [`Some`].
+    ///
+    /// [`String`]: https://doc.rust-lang.org/std/string/struct.String.html
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is a [`Some`], with a panic message including the
+    /// formatted message, and the content of the [`Some`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fmt;
+    /// use std::panic;
+    ///
+    /// use unwrap_none::UnwrapNone;
+    ///
+    /// let msg = String::from("duplicate key");
+    /// None::<i32>.expect_none_msg(&msg);
+    /// # #[cfg(feature = "enforce")] {
+    /// let err = panic::catch_unwind(|| Some(2).expect_none_msg(&msg)).unwrap_err();
+    /// assert_eq!(err.downcast_ref::<String>().unwrap(), "duplicate key: 2");
+    ///
+    /// struct DuplicateKey(i32);
+    ///
+    /// impl fmt::Display for DuplicateKey {
+    ///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    ///         write!(f, "duplicate key {}", self.0)
+    ///     }
+    /// }
+    ///
+    /// let err = panic::catch_unwind(|| Some(2).expect_none_msg(DuplicateKey(4))).unwrap_err();
+    /// assert_eq!(err.downcast_ref::<String>().unwrap(), "duplicate key 4: 2");
+    /// # }
+    /// ```
+    fn expect_none_msg<D>(self, msg: D)
+    where
+        T: fmt::Debug,
+        D: fmt::Display;
+
+    /// Consumes `self` while expecting [`None`] and returning nothing,
+    /// formatting the panic message into `w`.
+    ///
+    /// This gives full control over the buffer used for the panic message,
+    /// e.g. a fixed-capacity string on the stack, without needing an
+    /// allocator. The message is appended to the current contents of `w`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is a [`Some`], after writing the passed prefix and
+    /// the content of the [`Some`] to `w`, with the contents of `w` as panic
+    /// message.
+    ///
+    /// If writing to `w` fails, e.g. because it is full, the panic message is
+    /// only the passed prefix instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::{fmt, panic};
+    ///
+    /// use unwrap_none::UnwrapNone;
+    ///
+    /// struct Buffer {
+    ///     data: [u8; 32],
+    ///     len: usize,
+    /// }
+    ///
+    /// impl fmt::Write for Buffer {
+    ///     fn write_str(&mut self, s: &str) -> fmt::Result {
+    ///         let end = self.len + s.len();
+    ///         self.data.get_mut(self.len..end).ok_or(fmt::Error)?.copy_from_slice(s.as_bytes());
+    ///         self.len = end;
+    ///         Ok(())
+    ///     }
+    /// }
+    ///
+    /// impl AsRef<str> for Buffer {
+    ///     fn as_ref(&self) -> &str {
+    ///         core::str::from_utf8(&self.data[..self.len]).unwrap()
+    ///     }
+    /// }
+    ///
+    /// let mut buf = Buffer { data: [0; 32], len: 0 };
+    /// None::<i32>.expect_none_to(&mut buf, "slot is occupied");
+    /// assert_eq!(buf.as_ref(), "");
+    ///
+    /// # #[cfg(feature = "enforce")] {
+    /// let err = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+    ///     Some(5).expect_none_to(&mut buf, "slot is occupied")
+    /// }))
+    /// .unwrap_err();
+    /// assert_eq!(err.downcast_ref::<String>().unwrap(), "slot is occupied: 5");
+    ///
+    /// // The value does not fit in the remaining space.
+    /// let err = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+    ///     Some([0u8; 16]).expect_none_to(&mut buf, "slot is occupied")
+    /// }))
+    /// .unwrap_err();
+    /// assert_eq!(err.downcast_ref::<String>().unwrap(), "slot is occupied");
+    /// # }
+    /// ```
+    fn expect_none_to<W>(self, w: &mut W, prefix: &str)
+    where
+        T: fmt::Debug,
+        W: fmt::Write + AsRef<str>;
+
+    /// Consumes `self` while expecting [`None`] and returning nothing.
+    ///
+    /// This behaves like [`expect_none`](UnwrapNone::expect_none), except the
+    /// passed index is included in the panic message, e.g. to pinpoint which
+    /// element of an [`enumerate`](Iterator::enumerate)d loop failed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is a [`Some`], with a panic message including the
+    /// passed message, the passed index, and the content of the [`Some`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use std::panic;
+    ///
+    /// use unwrap_none::UnwrapNone;
+    ///
+    /// # #[cfg(feature = "enforce")] {
+    /// let mut map = HashMap::new();
+    /// let err = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+    ///     for (i, k) in [3, 1, 4, 1, 5].into_iter().enumerate() {
+    ///         map.insert(k, i).expect_none_at(i, "duplicate key");
+    ///     }
+    /// }))
+    /// .unwrap_err();
+    /// let msg = err.downcast_ref::<String>().unwrap();
+    /// assert_eq!(msg, "duplicate key at index 3: 1");
+    /// # }
+    /// ```
+    fn expect_none_at(self, index: usize, msg: &str)
+    where
+        T: fmt::Debug;
+
+    /// Consumes `self` while expecting [`None`] and returning nothing.
+    ///
+    /// This behaves like [`expect_none`](UnwrapNone::expect_none), except the
+    /// passed key is included in the panic message. This is useful for the
+    /// result of e.g. [`HashMap::insert`], which is the old value and not the
+    /// key that collided. The key is only formatted if the value is a
+    /// [`Some`].
+    ///
+    /// [`HashMap::insert`]: https://doc.rust-lang.org/std/collections/struct.HashMap.html#method.insert
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is a [`Some`], with a panic message including the
+    /// passed message, the passed key, and the content of the [`Some`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use std::panic;
+    ///
+    /// use unwrap_none::UnwrapNone;
+    ///
+    /// let mut map = HashMap::new();
+    /// map.insert(42, "old").expect_none_for_key(42, "duplicate entry");
+    ///
+    /// # #[cfg(feature = "enforce")] {
+    /// let err = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+    ///     map.insert(42, "new").expect_none_for_key(42, "duplicate entry")
+    /// }))
+    /// .unwrap_err();
+    /// let msg = err.downcast_ref::<String>().unwrap();
+    /// assert_eq!(msg, "duplicate entry for key 42: \"old\"");
+    /// # }
+    /// ```
+    fn expect_none_for_key<K>(self, key: K, msg: &str)
+    where
+        T: fmt::Debug,
+        K: fmt::Debug;
+
+    /// Consumes `self` while expecting [`None`] and returning nothing.
+    ///
+    /// This is like [`expect_none_for_key`](UnwrapNone::expect_none_for_key),
+    /// for keys that do not implement [`Debug`](fmt::Debug). Instead of the key
+    /// itself, the panic message includes a short hash of it, which tells
+    /// different keys apart. The hash is only computed if the value is a
+    /// [`Some`], and is the same across runs of the same build.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is a [`Some`], with a panic message including the
+    /// passed message, the hash of the key, and the content of the [`Some`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use std::panic;
+    ///
+    /// use unwrap_none::UnwrapNone;
+    ///
+    /// #[derive(Hash, PartialEq, Eq)]
+    /// struct Token([u8; 4]);
+    ///
+    /// let mut sessions = HashMap::new();
+    /// sessions.insert(Token(*b"abcd"), 1);
+    /// sessions.insert(Token(*b"efgh"), 1);
+    ///
+    /// let mut collide = |token: Token| {
+    ///     let msg = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+    ///         sessions.insert(Token(token.0), 3).expect_none_hashed(&token, "duplicate session")
+    ///     }))
+    ///     .unwrap_err();
+    ///     msg.downcast_ref::<String>().unwrap().clone()
+    /// };
+    /// # #[cfg(feature = "enforce")] {
+    /// let first = collide(Token(*b"abcd"));
+    /// let second = collide(Token(*b"efgh"));
+    /// assert!(first.starts_with("duplicate session for key with hash "));
+    /// assert!(first.ends_with(": 1"));
+    /// // Only the hash differs.
+    /// assert_ne!(first, second);
+    /// # }
+    /// ```
+    fn expect_none_hashed<H>(self, key: &H, msg: &str)
+    where
+        T: fmt::Debug,
+        H: core::hash::Hash + ?Sized;
+
+    /// Consumes `self` while expecting [`None`] and returning nothing.
+    ///
+    /// This is like [`expect_none`](UnwrapNone::expect_none), except the panic
+    /// message starts with a stable error code in brackets, which is easy to
+    /// search for in logs. `detail` and the value give the context.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is a [`Some`], with a panic message of the form
+    /// `[{code}] {detail}: Some({:?})`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use std::panic;
+    ///
+    /// use unwrap_none::UnwrapNone;
+    ///
+    /// let mut routes = HashMap::new();
+    /// routes.insert("/health", 1).expect_none_coded("E0042", "route registered twice");
+    ///
+    /// # #[cfg(feature = "enforce")] {
+    /// let err = panic::catch_unwind(move || {
+    ///     routes.insert("/health", 2).expect_none_coded("E0042", "route registered twice")
+    /// })
+    /// .unwrap_err();
+    /// let msg = err.downcast_ref::<String>().unwrap();
+    /// assert_eq!(msg, "[E0042] route registered twice: Some(1)");
+    /// # }
+    /// ```
+    fn expect_none_coded(self, code: &'static str, detail: &str)
+    where
+        T: fmt::Debug;
+
+    /// Consumes `self` while expecting [`None`] or a [`Some`] value for which
+    /// `pred` returns `true`, returning nothing.
+    ///
+    /// This is useful if a slot may only be occupied by e.g. a sentinel value.
+    /// `pred` is called before the value is treated as unexpected, so a
+    /// [`Some`] it accepts is neither counted nor logged.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is a [`Some`] and `pred` returns `false`, with a
+    /// panic message including the passed message, and the content of the
+    /// [`Some`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::panic;
+    ///
+    /// use unwrap_none::UnwrapNone;
+    ///
+    /// const SENTINEL: u32 = u32::MAX;
+    ///
+    /// None::<u32>.expect_absent(|&v| v == SENTINEL, "slot is in use");
+    /// Some(SENTINEL).expect_absent(|&v| v == SENTINEL, "slot is in use");
+    /// # #[cfg(feature = "metrics")]
+    /// # assert_eq!(unwrap_none::unexpected_some_count(), 0);
+    ///
+    /// # #[cfg(feature = "enforce")] {
+    /// let err = panic::catch_unwind(|| Some(3).expect_absent(|&v| v == SENTINEL, "slot is in use"))
+    ///     .unwrap_err();
+    /// assert_eq!(err.downcast_ref::<String>().unwrap(), "slot is in use: 3");
+    /// # }
+    /// ```
+    fn expect_absent<F>(self, pred: F, msg: &str)
+    where
+        T: fmt::Debug,
+        F: FnOnce(&T) -> bool;
+
+    /// Consumes `self` while expecting [`None`] or a [`Some`] value equal to
+    /// `sentinel`, returning nothing.
+    ///
+    /// This is [`expect_absent`](UnwrapNone::expect_absent) for the common
+    /// case of a slot that may at most hold a known default, e.g. for
+    /// idempotent inserts. `sentinel` is only compared if the value is a
+    /// [`Some`], and a [`Some`] equal to it is not treated as unexpected.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is a [`Some`] not equal to `sentinel`, with a panic
+    /// message including the passed message, and the content of the [`Some`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use std::panic;
+    ///
+    /// use unwrap_none::UnwrapNone;
+    ///
+    /// let mut map = HashMap::new();
+    /// map.insert(1, "default").expect_none_or_eq(&"default", "conflicting insert");
+    /// map.insert(1, "default").expect_none_or_eq(&"default", "conflicting insert");
+    /// # #[cfg(feature = "metrics")]
+    /// # assert_eq!(unwrap_none::unexpected_some_count(), 0);
+    ///
+    /// # #[cfg(feature = "enforce")] {
+    /// let err = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+    ///     map.insert(1, "custom").expect_none_or_eq(&"custom", "conflicting insert")
+    /// }))
+    /// .unwrap_err();
+    /// let msg = err.downcast_ref::<String>().unwrap();
+    /// assert_eq!(msg, "conflicting insert: \"default\"");
+    /// # }
+    /// ```
+    fn expect_none_or_eq(self, sentinel: &T, msg: &str)
+    where
+        T: PartialEq + fmt::Debug;
+
+    /// Consumes `self` while expecting [`None`], calling `on_some` with the
+    /// content of a [`Some`] before panicking.
+    ///
+    /// This combines [`unwrap_none_or_else`](UnwrapNone::unwrap_none_or_else)
+    /// and [`expect_none`](UnwrapNone::expect_none): the closure gets to
+    /// observe the value, e.g. to log it or dump related state, and the
+    /// panic is still the usual one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is a [`Some`] after `on_some` returns, with a panic
+    /// message including the passed message, and the content of the [`Some`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cell::Cell;
+    /// use std::panic;
+    ///
+    /// use unwrap_none::UnwrapNone;
+    ///
+    /// let seen = Cell::new(None::<i32>);
+    /// None.expect_none_or("slot in use", |val| seen.set(Some(*val)));
+    /// assert_eq!(seen.get(), None);
+    ///
+    /// # #[cfg(feature = "enforce")] {
+    /// let err = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+    ///     Some(7).expect_none_or("slot in use", |val| seen.set(Some(*val)))
+    /// }))
+    /// .unwrap_err();
+    /// assert_eq!(seen.get(), Some(7));
+    /// assert_eq!(err.downcast_ref::<String>().unwrap(), "slot in use: 7");
+    /// # }
+    /// ```
+    fn expect_none_or<F>(self, msg: &str, on_some: F)
+    where
+        T: fmt::Debug,
+        F: FnOnce(&T);
+
+    /// Consumes `self` while expecting [`None`] and `also` to return `true`,
+    /// returning nothing.
+    ///
+    /// This keeps checks like "the slot is empty and the counter is zero"
+    /// together. `also` is only called if the value is [`None`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is a [`Some`], with a panic message including the
+    /// passed message, and the content of the [`Some`]. `also` is not called
+    /// in this case, so the message cannot tell whether it would have failed
+    /// too.
+    ///
+    /// Panics if the value is [`None`] but `also` returns `false`, with a panic
+    /// message including the passed message and stating that the additional
+    /// check failed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::panic;
+    ///
+    /// use unwrap_none::UnwrapNone;
+    ///
+    /// let pending = 0;
+    /// None::<i32>.expect_none_and("slot is busy", || pending == 0);
+    ///
+    /// # #[cfg(feature = "enforce")] {
+    /// let err = panic::catch_unwind(|| None::<i32>.expect_none_and("slot is busy", || false))
+    ///     .unwrap_err();
+    /// let msg = err.downcast_ref::<String>().unwrap();
+    /// assert_eq!(msg, "slot is busy: additional check failed");
+    ///
+    /// for also in [true, false] {
+    ///     let mut called = false;
+    ///     let err = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+    ///         Some(5).expect_none_and("slot is busy", || {
+    ///             called = true;
+    ///             also
+    ///         })
+    ///     }))
+    ///     .unwrap_err();
+    ///     let msg = err.downcast_ref::<String>().unwrap();
+    ///     assert_eq!(msg, "slot is busy: expected None, got Some(5)");
+    ///     assert!(!called);
+    /// }
+    /// # }
+    /// ```
+    fn expect_none_and<F>(self, msg: &str, also: F)
+    where
+        T: fmt::Debug,
+        F: FnOnce() -> bool;
+
+    /// Consumes `self` while expecting [`None`], then calls `then` and
+    /// returns its result.
+    ///
+    /// This guarantees the check runs before `then` in a single expression.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is a [`Some`], with a panic message including the
+    /// passed message, and the content of the [`Some`]. `then` is not called
+    /// in this case.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::panic;
+    ///
+    /// use unwrap_none::UnwrapNone;
+    ///
+    /// let mut slot = None;
+    /// let len = slot.take().expect_none_then("slot is occupied", || {
+    ///     slot = Some(vec![0; 4]);
+    ///     4
+    /// });
+    /// assert_eq!(len, 4);
+    ///
+    /// let mut called = false;
+    /// # #[cfg(feature = "enforce")] {
+    /// let err = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+    ///     slot.take().expect_none_then("slot is occupied", || called = true)
+    /// }))
+    /// .unwrap_err();
+    /// let msg = err.downcast_ref::<String>().unwrap();
+    /// assert_eq!(msg, "slot is occupied: [0, 0, 0, 0]");
+    /// assert!(!called);
+    /// # }
+    /// ```
+    fn expect_none_then<R, F>(self, msg: &str, then: F) -> R
+    where
+        T: fmt::Debug,
+        F: FnOnce() -> R;
+
+    /// Consumes `self` while expecting [`None`], returning an [`IsNone`]
+    /// witness of the check.
+    ///
+    /// This is like [`expect_none`](UnwrapNone::expect_none), for type-state
+    /// patterns where a function only accepts input once emptiness is
+    /// established.
+    ///
+    /// # Panics
+    ///
+    /// Panics like [`expect_none`](UnwrapNone::expect_none) if the value is a
+    /// [`Some`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use unwrap_none::{IsNone, UnwrapNone};
+    ///
+    /// fn commit(_: IsNone) -> &'static str {
+    ///     "committed"
+    /// }
+    ///
+    /// let conflict: Option<u32> = None;
+    /// assert_eq!(commit(conflict.narrow_none("conflicting write")), "committed");
+    /// ```
+    ///
+    /// ```should_panic
+    /// use unwrap_none::UnwrapNone;
+    ///
+    /// let _proof = Some(3).narrow_none("conflicting write");
+    /// # #[cfg(not(feature = "enforce"))]
+    /// # panic!("the checks are disabled");
+    /// ```
+    fn narrow_none(self, msg: &str) -> IsNone
+    where
+        T: MaybeDebug;
+
+    /// Consumes `self` while expecting [`None`] and returning nothing.
+    ///
+    /// Unlike [`unwrap_none`](UnwrapNone::unwrap_none) the content of the
+    /// [`Some`] is never formatted, so it cannot leak into panic messages or
+    /// logs. As such `T` does not need to implement [`Debug`](fmt::Debug).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is a [`Some`], with a fixed panic message.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use unwrap_none::UnwrapNone;
+    ///
+    /// struct Token(&'static str);
+    ///
+    /// let token: Option<Token> = None;
+    /// token.unwrap_none_redacted();
+    /// ```
+    ///
+    /// ```
+    /// use std::panic;
+    ///
+    /// use unwrap_none::UnwrapNone;
+    ///
+    /// struct Token(&'static str);
+    ///
+    /// let token = Some(Token("hunter2"));
+    /// # #[cfg(feature = "enforce")] {
+    /// let err = panic::catch_unwind(|| token.unwrap_none_redacted()).unwrap_err();
+    /// let msg = err.downcast_ref::<String>().unwrap();
+    /// assert!(!msg.contains("hunter2"));
+    /// # }
+    /// ```
+    fn unwrap_none_redacted(self);
+
+    /// Consumes `self` while expecting [`None`] and returning nothing.
+    ///
+    /// Unlike [`expect_none`](UnwrapNone::expect_none) the panic message is
+    /// exactly the passed message, without the content of the [`Some`]. This
+    /// makes it easy to match in `#[should_panic(expected = "...")]` tests,
+    /// even if the [`Debug`](fmt::Debug) output of `T` varies. As such `T`
+    /// does not need to implement [`Debug`](fmt::Debug).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is a [`Some`], with the passed message as panic
+    /// message, prefixed only with the context pushed with
+    /// `push_none_context`, if any.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::panic;
+    ///
+    /// use unwrap_none::UnwrapNone;
+    ///
+    /// None::<u32>.expect_none_exact("slot is taken");
+    ///
+    /// # #[cfg(feature = "enforce")] {
+    /// let err = panic::catch_unwind(|| Some(7).expect_none_exact("slot is taken")).unwrap_err();
+    /// assert_eq!(err.downcast_ref::<String>().unwrap(), "slot is taken");
+    /// # }
+    /// ```
+    ///
+    /// ```should_panic
+    /// use std::time::Instant;
+    ///
+    /// use unwrap_none::UnwrapNone;
+    ///
+    /// // This will panic with exactly "timer already started".
+    /// Some(Instant::now()).expect_none_exact("timer already started");
+    /// # #[cfg(not(feature = "enforce"))]
+    /// # panic!("the checks are disabled");
+    /// ```
+    fn expect_none_exact(self, msg: &str);
+
+    /// Consumes `self` while expecting [`None`] and returning nothing, but only
+    /// checks if debug assertions are enabled.
+    ///
+    /// This is to [`unwrap_none`](UnwrapNone::unwrap_none) what
+    /// [`debug_assert!`] is to [`assert!`]. With debug assertions disabled
+    /// this does nothing beyond dropping the value. The value is dropped
+    /// either way.
+    ///
+    /// # Panics
+    ///
+    /// Panics if debug assertions are enabled and the value is a [`Some`],
+    /// with a panic message including the content of the [`Some`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use unwrap_none::UnwrapNone;
+    ///
+    /// let input: Option<i32> = None;
+    /// input.debug_unwrap_none();
+    /// ```
+    ///
+    /// ```
+    /// use std::panic;
+    ///
+    /// use unwrap_none::UnwrapNone;
+    ///
+    /// // This only panics in builds with debug assertions enabled.
+    /// let result = panic::catch_unwind(|| Some(10).debug_unwrap_none());
+    /// # use unwrap_none::macros::{DEBUG_ASSERTIONS, ENFORCE};
+    /// # assert_eq!(result.is_err(), ENFORCE && DEBUG_ASSERTIONS);
+    /// ```
+    fn debug_unwrap_none(self)
+    where
+        T: fmt::Debug;
+
+    /// Consumes `self` while assuming it is [`None`], without checking.
+    ///
+    /// This allows the compiler to remove the check done by
+    /// [`unwrap_none`](UnwrapNone::unwrap_none) entirely.
+    ///
+    /// # Safety
+    ///
+    /// Calling this method on a [`Some`] is *[undefined behavior]*.
+    ///
+    /// [undefined behavior]: https://doc.rust-lang.org/reference/behavior-considered-undefined.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use unwrap_none::UnwrapNone;
+    ///
+    /// let input: Option<i32> = None;
+    /// unsafe { input.unwrap_none_unchecked() };
+    /// ```
+    unsafe fn unwrap_none_unchecked(self);
+
+    /// Expects `self` to be [`None`] without consuming it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is a [`Some`], with a panic message including the
+    /// content of the [`Some`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use unwrap_none::UnwrapNone;
+    ///
+    /// let mut slot: Option<String> = None;
+    /// slot.assert_none();
+    ///
+    /// // `slot` can still be used afterwards.
+    /// slot = Some("filled".to_string());
+    /// assert_eq!(slot.as_deref(), Some("filled"));
+    /// ```
+    ///
+    /// ```should_panic
+    /// # use unwrap_none::UnwrapNone;
+    /// #
+    /// let slot = Some("filled".to_string());
+    /// slot.assert_none();
+    /// # #[cfg(not(feature = "enforce"))]
+    /// # panic!("the checks are disabled");
+    /// ```
+    fn assert_none(&self)
+    where
+        T: fmt::Debug;
+
+    /// Expects [`None`] without consuming `self`, returning a clone of it.
+    ///
+    /// This is like [`assert_none`](UnwrapNone::assert_none), except it
+    /// returns an owned copy so the check and the copy fit in a single
+    /// expression.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is a [`Some`], with a panic message including the
+    /// passed message, and the content of the [`Some`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use unwrap_none::UnwrapNone;
+    ///
+    /// let slot: Option<String> = None;
+    /// let copy = slot.expect_none_cloned("slot is occupied");
+    /// assert_eq!(copy, slot);
+    /// assert_eq!(slot, None);
+    /// ```
+    ///
+    /// ```
+    /// use std::panic;
+    ///
+    /// use unwrap_none::UnwrapNone;
+    ///
+    /// let slot = Some("filled".to_string());
+    /// # #[cfg(feature = "enforce")] {
+    /// let err = panic::catch_unwind(|| slot.expect_none_cloned("slot is occupied")).unwrap_err();
+    /// let msg = err.downcast_ref::<String>().unwrap();
+    /// assert_eq!(msg, "slot is occupied: \"filled\"");
+    /// // The original is untouched.
+    /// assert_eq!(slot.as_deref(), Some("filled"));
+    /// # }
+    /// ```
+    fn expect_none_cloned(&self, msg: &str) -> Option<T>
+    where
+        T: Clone + fmt::Debug;
+
+    /// Takes the value out of `self`, leaving [`None`] in its place, while
+    /// expecting it to have been [`None`] already.
+    ///
+    /// `self` is [`None`] afterwards even if this panics.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value was a [`Some`], with a panic message including the
+    /// passed message, and the content of the [`Some`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use unwrap_none::UnwrapNone;
+    ///
+    /// let mut pending: Option<u32> = None;
+    /// pending.take_expect_none("request already pending");
+    /// assert_eq!(pending, None);
+    /// ```
+    ///
+    /// ```
+    /// use std::panic::{self, AssertUnwindSafe};
+    ///
+    /// use unwrap_none::UnwrapNone;
+    ///
+    /// let mut pending = Some(7);
+    /// # #[cfg(feature = "enforce")] {
+    /// let err = panic::catch_unwind(AssertUnwindSafe(|| {
+    ///     pending.take_expect_none("request already pending")
+    /// }))
+    /// .unwrap_err();
+    /// let msg = err.downcast_ref::<String>().unwrap();
+    /// # #[cfg(not(feature = "minimal-panic"))]
+    /// assert_eq!(msg, "request already pending: 7");
+    /// assert_eq!(pending, None);
+    /// # }
+    /// ```
+    fn take_expect_none(&mut self, msg: &str)
+    where
+        T: fmt::Debug;
+
+    /// Fills `self` with the value returned by `f` while expecting it to be
+    /// [`None`], and returns a mutable reference to the new value.
+    ///
+    /// `f` is only called if the value is [`None`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is a [`Some`], with a panic message including the
+    /// content of the [`Some`]. `self` is left unchanged in that case.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use unwrap_none::UnwrapNone;
+    ///
+    /// let mut config: Option<Vec<u8>> = None;
+    /// config.or_insert_expecting_none(Vec::new).push(1);
+    /// assert_eq!(config, Some(vec![1]));
+    /// ```
+    ///
+    /// ```
+    /// use std::panic::{self, AssertUnwindSafe};
+    ///
+    /// use unwrap_none::UnwrapNone;
+    ///
+    /// let mut config = Some(vec![1]);
+    /// # #[cfg(feature = "enforce")] {
+    /// let err = panic::catch_unwind(AssertUnwindSafe(|| {
+    ///     config.or_insert_expecting_none(Vec::new);
+    /// }))
+    /// .unwrap_err();
+    /// let msg = err.downcast_ref::<String>().unwrap();
+    /// assert!(msg.ends_with("on a `Some` value: [1]"));
+    /// assert_eq!(config, Some(vec![1]));
+    /// # }
+    /// ```
+    fn or_insert_expecting_none<F>(&mut self, f: F) -> &mut T
+    where
+        T: fmt::Debug,
+        F: FnOnce() -> T;
+
+    /// Transforms `self` into a [`Result`], mapping [`None`] to `Ok(())` and
+    /// [`Some`] to `Err(err)`, discarding the content of the [`Some`].
+    ///
+    /// This is the counterpart of [`Option::ok_or`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    ///
+    /// use unwrap_none::UnwrapNone;
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// enum Error {
+    ///     DuplicateKey,
+    /// }
+    ///
+    /// fn insert_all(map: &mut HashMap<i32, i32>, values: &[i32]) -> Result<(), Error> {
+    ///     for &i in values {
+    ///         map.insert(i * i, i).none_ok_or(Error::DuplicateKey)?;
+    ///     }
+    ///     Ok(())
+    /// }
+    ///
+    /// let mut map = HashMap::new();
+    /// assert_eq!(insert_all(&mut map, &[1, 2, 3]), Ok(()));
+    ///
+    /// let mut map = HashMap::new();
+    /// assert_eq!(insert_all(&mut map, &[1, 2, -2]), Err(Error::DuplicateKey));
+    /// ```
+    fn none_ok_or<E>(self, err: E) -> Result<(), E>;
+
+    /// Transforms `self` into a [`Result`], mapping [`None`] to `Ok(())` and
+    /// [`Some(v)`] to `Err(f(v))`.
+    ///
+    /// `f` is only called if the value is a [`Some`]. This is the counterpart
+    /// of [`Option::ok_or_else`].
+    ///
+    /// [`Some(v)`]: Some
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    ///
+    /// use unwrap_none::UnwrapNone;
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// struct DuplicateKey {
+    ///     key: i32,
+    ///     old: i32,
+    /// }
+    ///
+    /// fn insert_all(map: &mut HashMap<i32, i32>, values: &[i32]) -> Result<(), DuplicateKey> {
+    ///     for &i in values {
+    ///         map.insert(i * i, i)
+    ///             .none_ok_or_else(|old| DuplicateKey { key: i * i, old })?;
+    ///     }
+    ///     Ok(())
+    /// }
+    ///
+    /// let mut map = HashMap::new();
+    /// assert_eq!(insert_all(&mut map, &[1, 2, 3]), Ok(()));
+    ///
+    /// let mut map = HashMap::new();
+    /// assert_eq!(
+    ///     insert_all(&mut map, &[1, 2, -2]),
+    ///     Err(DuplicateKey { key: 4, old: 2 }),
+    /// );
+    /// ```
+    fn none_ok_or_else<E, F>(self, f: F) -> Result<(), E>
+    where
+        F: FnOnce(T) -> E;
+
+    /// Transforms `self` into a [`Result`], mapping [`None`] to `Ok(())` and
+    /// [`Some`] to `Err(code)`, discarding the content of the [`Some`].
+    ///
+    /// This is [`none_ok_or`](UnwrapNone::none_ok_or) for firmware reporting
+    /// numeric fault codes instead of formatted messages.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use unwrap_none::UnwrapNone;
+    ///
+    /// const FAULT_IRQ_REGISTERED: u32 = 0x12;
+    ///
+    /// fn register(handler: &mut Option<fn()>, new: fn()) -> Result<(), u32> {
+    ///     handler.replace(new).unwrap_none_or_fault(FAULT_IRQ_REGISTERED)
+    /// }
+    ///
+    /// let mut handler = None;
+    /// assert_eq!(register(&mut handler, || {}), Ok(()));
+    /// match register(&mut handler, || {}) {
+    ///     Ok(()) => unreachable!(),
+    ///     Err(FAULT_IRQ_REGISTERED) => {}
+    ///     Err(code) => panic!("unknown fault {:#x}", code),
+    /// }
+    /// ```
+    fn unwrap_none_or_fault(self, code: u32) -> Result<(), u32>;
+
+    /// Transforms `self` into a [`Result`], mapping [`None`] to `Ok(())` and
+    /// [`Some(v)`] to `Err((location, v))`, where `location` is the location
+    /// of the caller.
+    ///
+    /// This allows building custom error messages that still point at the
+    /// call site, without panicking.
+    ///
+    /// [`Some(v)`]: Some
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use unwrap_none::UnwrapNone;
+    ///
+    /// assert!(None::<i32>.none_or_location().is_ok());
+    ///
+    /// let line = line!() + 1;
+    /// let (location, val) = Some(5).none_or_location().unwrap_err();
+    /// assert_eq!(location.line(), line);
+    /// assert_eq!(location.file(), file!());
+    /// assert_eq!(val, 5);
+    /// ```
+    fn none_or_location(self) -> Result<(), (&'static core::panic::Location<'static>, T)>;
+
+    /// Calls the supplied closure with a reference to the contained value
+    /// only if the instance is [`Some`], then returns the instance unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use unwrap_none::UnwrapNone;
+    ///
+    /// let mut seen = Vec::new();
+    ///
+    /// // The value is passed on unchanged after calling the closure.
+    /// let input = Some(10).inspect_some(|val| seen.push(*val));
+    /// assert_eq!(input, Some(10));
+    ///
+    /// // The closure is not called because the input is `None`.
+    /// let input = None.inspect_some(|val| seen.push(*val));
+    /// assert_eq!(input, None);
+    ///
+    /// assert_eq!(seen, [10]);
+    /// ```
+    fn inspect_some<F>(self, f: F) -> Option<T>
+    where
+        F: FnOnce(&T);
+
+    /// Calls the supplied closure only if the instance is [`None`], then
+    /// returns the instance unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use unwrap_none::UnwrapNone;
+    ///
+    /// let mut empty = 0;
+    ///
+    /// // The closure is called because the input is `None`.
+    /// let input = None::<i32>.inspect_none(|| empty += 1);
+    /// assert_eq!(input, None);
+    ///
+    /// // The value is passed on unchanged without calling the closure.
+    /// let input = Some(10).inspect_none(|| empty += 1);
+    /// assert_eq!(input, Some(10));
+    ///
+    /// assert_eq!(empty, 1);
+    /// ```
+    fn inspect_none<F>(self, f: F) -> Option<T>
+    where
+        F: FnOnce();
+
+    /// Returns `U::default()` if the instance is [`None`], or calls the
+    /// supplied closure with the contained value otherwise.
+    ///
+    /// `f` is only called if the value is a [`Some`]. This is useful when an
+    /// unexpected value can still be folded into a meaningful result.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    ///
+    /// use unwrap_none::UnwrapNone;
+    ///
+    /// let mut map = HashMap::new();
+    ///
+    /// // Nothing was replaced, so there is nothing to report.
+    /// let replaced: Vec<String> = map
+    ///     .insert("key", 1)
+    ///     .map_some_or_default(|old| vec![format!("replaced {}", old)]);
+    /// assert!(replaced.is_empty());
+    ///
+    /// let replaced: Vec<String> = map
+    ///     .insert("key", 2)
+    ///     .map_some_or_default(|old| vec![format!("replaced {}", old)]);
+    /// assert_eq!(replaced, ["replaced 1"]);
+    /// ```
+    fn map_some_or_default<U, F>(self, f: F) -> U
+    where
+        U: Default,
+        F: FnOnce(T) -> U;
+
+    /// Returns `default` if the instance is [`None`], or calls the supplied
+    /// closure with the contained value otherwise.
+    ///
+    /// This is like [`Option::map_or`], framed with [`None`] as the expected
+    /// case. `default` is evaluated eagerly, use
+    /// [`map_or_else_none`](UnwrapNone::map_or_else_none) to compute it lazily.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use unwrap_none::UnwrapNone;
+    ///
+    /// assert_eq!(None.map_or_none("empty", |_: u32| "occupied"), "empty");
+    /// assert_eq!(Some(3).map_or_none(0, |v| v * 2), 6);
+    /// ```
+    fn map_or_none<U, F>(self, default: U, f: F) -> U
+    where
+        F: FnOnce(T) -> U;
+
+    /// Calls `default` if the instance is [`None`], or calls `f` with the
+    /// contained value otherwise.
+    ///
+    /// This is like [`Option::map_or_else`], framed with [`None`] as the
+    /// expected case. Only one of the closures is called.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use unwrap_none::UnwrapNone;
+    ///
+    /// let describe = |slot: Option<u32>| {
+    ///     slot.map_or_else_none(|| String::from("empty"), |v| format!("occupied by {}", v))
+    /// };
+    /// assert_eq!(describe(None), "empty");
+    /// assert_eq!(describe(Some(3)), "occupied by 3");
+    /// ```
+    fn map_or_else_none<U, D, F>(self, default: D, f: F) -> U
+    where
+        D: FnOnce() -> U,
+        F: FnOnce(T) -> U;
+
+    /// Transforms `self` into a [`ControlFlow`], mapping [`None`] to
+    /// `Continue(())` and [`Some(v)`] to `Break(f(v))`.
+    ///
+    /// `f` is only called if the value is a [`Some`].
+    ///
+    /// [`ControlFlow`]: core::ops::ControlFlow
+    /// [`Some(v)`]: Some
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use std::ops::ControlFlow;
+    ///
+    /// use unwrap_none::UnwrapNone;
+    ///
+    /// let mut map = HashMap::new();
+    /// let mut duplicate = None;
+    /// for i in [1, 2, -2, 3] {
+    ///     if let ControlFlow::Break(old) = map.insert(i * i, i).unwrap_none_or_break(|old| old) {
+    ///         duplicate = Some((i, old));
+    ///         break;
+    ///     }
+    /// }
+    /// assert_eq!(duplicate, Some((-2, 2)));
+    ///
+    /// // It also composes with `try_for_each`.
+    /// let mut map = HashMap::new();
+    /// let flow = [1, 2, 3]
+    ///     .into_iter()
+    ///     .try_for_each(|i| map.insert(i, i).unwrap_none_or_break(|old| old));
+    /// assert_eq!(flow, ControlFlow::Continue(()));
+    /// ```
+    fn unwrap_none_or_break<B, F>(self, f: F) -> ControlFlow<B>
+    where
+        F: FnOnce(T) -> B;
+
+    /// Consumes `self` while expecting [`None`], returning the contained
+    /// value wrapped in an [`UnwrapNoneError`] otherwise.
+    ///
+    /// This is like [`try_unwrap_none`](UnwrapNone::try_unwrap_none), but the
+    /// error implements [`std::error::Error`].
+    ///
+    /// Available with the `std` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use std::error::Error;
+    ///
+    /// use unwrap_none::UnwrapNone;
+    ///
+    /// fn insert_all(map: &mut HashMap<i32, i32>, values: &[i32]) -> Result<(), Box<dyn Error>> {
+    ///     for &i in values {
+    ///         map.insert(i * i, i).try_unwrap_none_err()?;
+    ///     }
+    ///     Ok(())
+    /// }
+    ///
+    /// let mut map = HashMap::new();
+    /// assert!(insert_all(&mut map, &[1, 2, 3]).is_ok());
+    ///
+    /// let mut map = HashMap::new();
+    /// let err = insert_all(&mut map, &[1, 2, -2]).unwrap_err();
+    /// assert_eq!(err.to_string(), "expected None, got Some(2)");
+    /// ```
+    #[cfg(feature = "std")]
+    fn try_unwrap_none_err(self) -> Result<(), UnwrapNoneError<T>>;
+
+    /// Consumes `self` while expecting [`None`], returning a
+    /// [`miette::Report`] of an [`UnwrapNoneDiagnostic`] otherwise.
+    ///
+    /// Available with the `miette` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    ///
+    /// use unwrap_none::UnwrapNone;
+    ///
+    /// fn insert_all(map: &mut HashMap<i32, i32>, values: &[i32]) -> miette::Result<()> {
+    ///     for &i in values {
+    ///         map.insert(i * i, i).try_unwrap_none_diagnostic()?;
+    ///     }
+    ///     Ok(())
+    /// }
+    ///
+    /// let mut map = HashMap::new();
+    /// assert!(insert_all(&mut map, &[1, 2, 3]).is_ok());
+    ///
+    /// let mut map = HashMap::new();
+    /// let report = insert_all(&mut map, &[1, 2, -2]).unwrap_err();
+    /// assert_eq!(report.to_string(), "expected None, got Some(2)");
+    /// ```
+    #[cfg(feature = "miette")]
+    fn try_unwrap_none_diagnostic(self) -> miette::Result<()>
+    where
+        T: fmt::Debug;
+
+    /// Consumes `self` while expecting [`None`], returning an
+    /// [`anyhow::Error`] including the content of the [`Some`] otherwise.
+    ///
+    /// The value is formatted into the error message, so `T` does not need
+    /// to be `'static`.
+    ///
+    /// Available with the `anyhow` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    ///
+    /// use anyhow::Context;
+    /// use unwrap_none::UnwrapNone;
+    ///
+    /// fn insert_all(map: &mut HashMap<i32, i32>, values: &[i32]) -> anyhow::Result<()> {
+    ///     for &i in values {
+    ///         map.insert(i * i, i)
+    ///             .try_unwrap_none_anyhow()
+    ///             .with_context(|| format!("duplicate key {}", i * i))?;
+    ///     }
+    ///     Ok(())
+    /// }
+    ///
+    /// let mut map = HashMap::new();
+    /// assert!(insert_all(&mut map, &[1, 2, 3]).is_ok());
+    ///
+    /// let mut map = HashMap::new();
+    /// let err = insert_all(&mut map, &[1, 2, -2]).unwrap_err();
+    /// assert_eq!(err.to_string(), "duplicate key 4");
+    /// assert_eq!(err.root_cause().to_string(), "expected None, got Some(2)");
+    /// ```
+    #[cfg(feature = "anyhow")]
+    fn try_unwrap_none_anyhow(self) -> anyhow::Result<()>
+    where
+        T: fmt::Debug;
+
+    /// Consumes `self` while expecting [`None`], returning a
+    /// [`color_eyre::Report`] including the content of the [`Some`] and a
+    /// suggestion otherwise.
+    ///
+    /// The suggestion is only shown if `color-eyre` is installed as the global
+    /// report handler with [`color_eyre::install`].
+    ///
+    /// Available with the `color-eyre` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    ///
+    /// use unwrap_none::UnwrapNone;
+    ///
+    /// color_eyre::install().unwrap();
+    ///
+    /// let mut map = HashMap::new();
+    /// assert!(map.insert(1, "one").try_unwrap_none_eyre().is_ok());
+    ///
+    /// let report = map.insert(1, "uno").try_unwrap_none_eyre().unwrap_err();
+    /// assert_eq!(report.to_string(), "expected None, got Some(\"one\")");
+    /// assert!(format!("{:?}", report).contains("make sure the slot is empty"));
+    /// ```
+    #[cfg(feature = "color-eyre")]
+    fn try_unwrap_none_eyre(self) -> color_eyre::Result<()>
+    where
+        T: fmt::Debug;
+
+    /// Consumes `self` while expecting [`None`], returning a [`syn::Error`]
+    /// attached to `span` and including the content of the [`Some`] otherwise.
+    ///
+    /// This is meant for procedural macros, which should report errors
+    /// with [`syn::Error::to_compile_error`] instead of panicking.
+    ///
+    /// Available with the `syn` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use proc_macro2::Span;
+    /// use unwrap_none::UnwrapNone;
+    ///
+    /// let span = Span::call_site();
+    /// assert!(None::<&str>.try_unwrap_none_spanned(span).is_ok());
+    ///
+    /// let err = Some("repr").try_unwrap_none_spanned(span).unwrap_err();
+    /// assert_eq!(err.to_string(), "expected None, got Some(\"repr\")");
+    /// assert_eq!(format!("{:?}", err.span()), format!("{:?}", span));
+    /// assert!(err.to_compile_error().to_string().contains("compile_error"));
+    /// ```
+    #[cfg(feature = "syn")]
+    fn try_unwrap_none_spanned<S>(self, span: S) -> Result<(), syn::Error>
+    where
+        T: fmt::Debug,
+        S: Into<proc_macro2::Span>;
+
+    /// Consumes `self` while expecting [`None`], logging an error including
+    /// the content of the [`Some`] otherwise.
+    ///
+    /// This does not panic. The error is logged with the crate name as target,
+    /// use [`unwrap_none_or_log_target`](UnwrapNone::unwrap_none_or_log_target)
+    /// to use a different target.
+    ///
+    /// Available with the `log` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::Mutex;
+    ///
+    /// use log::{Level, LevelFilter, Log, Metadata, Record};
+    /// use unwrap_none::UnwrapNone;
+    ///
+    /// struct Capture(Mutex<Vec<String>>);
+    ///
+    /// impl Log for Capture {
+    ///     fn enabled(&self, _: &Metadata) -> bool {
+    ///         true
+    ///     }
+    ///
+    ///     fn log(&self, record: &Record) {
+    ///         assert_eq!(record.level(), Level::Error);
+    ///         let msg = format!("{}: {}", record.target(), record.args());
+    ///         self.0.lock().unwrap().push(msg);
+    ///     }
+    ///
+    ///     fn flush(&self) {}
+    /// }
+    ///
+    /// static LOGGER: Capture = Capture(Mutex::new(Vec::new()));
+    /// log::set_logger(&LOGGER).unwrap();
+    /// log::set_max_level(LevelFilter::Error);
+    ///
+    /// None::<i32>.unwrap_none_or_log();
+    /// Some(5).unwrap_none_or_log();
+    /// Some(6).unwrap_none_or_log_target("inserts");
+    ///
+    /// assert_eq!(
+    ///     *LOGGER.0.lock().unwrap(),
+    ///     [
+    ///         "unwrap_none: expected None, got Some(5)",
+    ///         "inserts: expected None, got Some(6)",
+    ///     ],
+    /// );
+    /// ```
+    #[cfg(feature = "log")]
+    fn unwrap_none_or_log(self)
+    where
+        T: fmt::Debug;
+
+    /// Consumes `self` while expecting [`None`], logging an error including
+    /// the content of the [`Some`] with the given target otherwise.
+    ///
+    /// See [`unwrap_none_or_log`](UnwrapNone::unwrap_none_or_log).
+    ///
+    /// Available with the `log` feature.
+    #[cfg(feature = "log")]
+    fn unwrap_none_or_log_target(self, target: &str)
+    where
+        T: fmt::Debug;
+
+    /// Consumes `self` while expecting [`None`], logging a warning including
+    /// the content of the [`Some`] and the location of the caller otherwise.
+    ///
+    /// Only the first [`Some`] seen at each call site is logged, any later
+    /// ones are ignored. Up to 64 call sites are remembered, beyond that every
+    /// [`Some`] is logged.
+    ///
+    /// Available with the `log` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::Mutex;
+    ///
+    /// use log::{Level, LevelFilter, Log, Metadata, Record};
+    /// use unwrap_none::UnwrapNone;
+    ///
+    /// struct Capture(Mutex<Vec<String>>);
+    ///
+    /// impl Log for Capture {
+    ///     fn enabled(&self, _: &Metadata) -> bool {
+    ///         true
+    ///     }
+    ///
+    ///     fn log(&self, record: &Record) {
+    ///         assert_eq!(record.level(), Level::Warn);
+    ///         self.0.lock().unwrap().push(record.args().to_string());
+    ///     }
+    ///
+    ///     fn flush(&self) {}
+    /// }
+    ///
+    /// static LOGGER: Capture = Capture(Mutex::new(Vec::new()));
+    /// log::set_logger(&LOGGER).unwrap();
+    /// log::set_max_level(LevelFilter::Warn);
+    ///
+    /// for i in 0..3 {
+    ///     Some(i).unwrap_none_or_warn_once();
+    /// }
+    /// Some(10).unwrap_none_or_warn_once();
+    ///
+    /// let logged = LOGGER.0.lock().unwrap();
+    /// assert_eq!(logged.len(), 2);
+    /// assert!(logged[0].starts_with("expected None, got Some(0) at "));
+    /// assert!(logged[1].starts_with("expected None, got Some(10) at "));
+    /// ```
+    #[cfg(feature = "log")]
+    fn unwrap_none_or_warn_once(self)
+    where
+        T: fmt::Debug;
+
+    /// Consumes `self` while expecting [`None`], emitting an error event
+    /// otherwise.
+    ///
+    /// This does not panic. The event has a `value` field with the content of
+    /// the [`Some`] and a `location` field with the location of the caller.
+    ///
+    /// Available with the `tracing` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fmt::{self, Write};
+    /// use std::sync::{Arc, Mutex};
+    ///
+    /// use tracing::field::{Field, Visit};
+    /// use tracing::span::{Attributes, Id, Record};
+    /// use tracing::{Event, Level, Metadata, Subscriber};
+    /// use unwrap_none::UnwrapNone;
+    ///
+    /// struct Capture(Arc<Mutex<Vec<String>>>);
+    ///
+    /// impl Subscriber for Capture {
+    ///     fn enabled(&self, _: &Metadata) -> bool {
+    ///         true
+    ///     }
+    ///
+    ///     fn event(&self, event: &Event) {
+    ///         assert_eq!(*event.metadata().level(), Level::ERROR);
+    ///         let mut fields = Fields(String::new());
+    ///         event.record(&mut fields);
+    ///         self.0.lock().unwrap().push(fields.0);
+    ///     }
+    ///
+    ///     fn new_span(&self, _: &Attributes) -> Id {
+    ///         Id::from_u64(1)
+    ///     }
+    ///
+    ///     fn record(&self, _: &Id, _: &Record) {}
+    ///     fn record_follows_from(&self, _: &Id, _: &Id) {}
+    ///     fn enter(&self, _: &Id) {}
+    ///     fn exit(&self, _: &Id) {}
+    /// }
+    ///
+    /// struct Fields(String);
+    ///
+    /// impl Visit for Fields {
+    ///     fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+    ///         write!(self.0, "{}={:?} ", field, value).unwrap();
+    ///     }
+    /// }
+    ///
+    /// let events = Arc::new(Mutex::new(Vec::new()));
+    /// let mut line = 0;
+    /// tracing::subscriber::with_default(Capture(events.clone()), || {
+    ///     None::<i32>.unwrap_none_or_trace();
+    ///     line = line!(); Some(5).unwrap_none_or_trace();
+    /// });
+    ///
+    /// let events = events.lock().unwrap();
+    /// assert_eq!(events.len(), 1);
+    /// assert!(events[0].contains("value=5 "));
+    /// assert!(events[0].contains(&format!(":{}:", line)));
+    /// ```
+    #[cfg(feature = "tracing")]
+    fn unwrap_none_or_trace(self)
+    where
+        T: fmt::Debug;
+
+    /// Consumes `self` while expecting [`None`], capturing a Sentry event
+    /// otherwise.
+    ///
+    /// This does not panic. The event is an error-level message including the
+    /// content of the [`Some`] and the location of the caller, and is sent to
+    /// the current Sentry hub.
+    ///
+    /// Available with the `sentry` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sentry_core::Level;
+    /// use unwrap_none::UnwrapNone;
+    ///
+    /// let mut line = 0;
+    /// let events = sentry_core::test::with_captured_events(|| {
+    ///     None::<i32>.unwrap_none_or_capture();
+    ///     line = line!(); Some(5).unwrap_none_or_capture();
+    /// });
+    ///
+    /// assert_eq!(events.len(), 1);
+    /// assert_eq!(events[0].level, Level::Error);
+    /// let msg = events[0].message.as_deref().unwrap();
+    /// assert!(msg.starts_with("expected None, got Some(5) at "));
+    /// assert!(msg.contains(&format!(":{}:", line)));
+    /// ```
+    #[cfg(feature = "sentry")]
+    fn unwrap_none_or_capture(self)
+    where
+        T: fmt::Debug;
+
+    /// Consumes `self` while expecting [`None`] and returning nothing.
+    ///
+    /// This is like [`unwrap_none`](UnwrapNone::unwrap_none), except the
+    /// content of the [`Some`] is formatted with [`defmt`] instead of
+    /// [`core::fmt`].
+    ///
+    /// Available with the `defmt` feature.
+    ///
+    /// # Panics
+    ///
+    /// Panics with [`defmt::panic!`] if the value is a [`Some`], with a panic
+    /// message including the content of the [`Some`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use unwrap_none::UnwrapNone;
+    ///
+    /// #[derive(defmt::Format)]
+    /// struct Reading {
+    ///     channel: u8,
+    ///     value: u16,
+    /// }
+    ///
+    /// let mut slot: Option<Reading> = None;
+    /// slot.take().unwrap_none_defmt();
+    /// slot = Some(Reading { channel: 1, value: 512 });
+    /// // This will panic, since `slot` is a `Some`.
+    /// slot.unwrap_none_defmt();
+    /// # #[defmt::global_logger]
+    /// # struct Logger;
+    /// # unsafe impl defmt::Logger for Logger {
+    /// #     fn acquire() {}
+    /// #     unsafe fn flush() {}
+    /// #     unsafe fn release() {}
+    /// #     unsafe fn write(_: &[u8]) {}
+    /// # }
+    /// # #[defmt::panic_handler]
+    /// # fn panic() -> ! {
+    /// #     loop {}
+    /// # }
+    /// ```
+    #[cfg(feature = "defmt")]
+    fn unwrap_none_defmt(self)
+    where
+        T: defmt::Format;
+
+    /// Consumes `self` while expecting [`None`] and returning nothing. The
+    /// content of the [`Some`] is formatted with [`ufmt`] instead of
+    /// [`core::fmt`].
+    ///
+    /// The formatted value is limited to 128 bytes, as it is written to a
+    /// buffer on the stack.
+    ///
+    /// Available with the `ufmt` feature.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is a [`Some`], with a panic message including the
+    /// content of the [`Some`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::panic;
+    ///
+    /// use ufmt::derive::uDebug;
+    /// use unwrap_none::UnwrapNone;
+    ///
+    /// #[derive(uDebug)]
+    /// struct Reading {
+    ///     channel: u8,
+    ///     value: u16,
+    /// }
+    ///
+    /// None::<Reading>.unwrap_none_ufmt();
+    ///
+    /// let slot = Some(Reading { channel: 1, value: 512 });
+    /// # #[cfg(feature = "enforce")] {
+    /// let err = panic::catch_unwind(|| slot.unwrap_none_ufmt()).unwrap_err();
+    /// let msg = err.downcast_ref::<String>().unwrap();
+    /// assert!(msg.ends_with(": Reading { channel: 1, value: 512 }"));
+    /// # }
+    /// ```
+    #[cfg(feature = "ufmt")]
+    fn unwrap_none_ufmt(self)
+    where
+        T: ufmt::uDebug;
+
+    /// Consumes `self` while expecting [`None`] and returning nothing.
+    ///
+    /// This is like [`expect_none`](UnwrapNone::expect_none), except it takes
+    /// an owned message. Note that the message is built by the caller even if
+    /// the value is [`None`]. Use
+    /// [`expect_none_with`](UnwrapNone::expect_none_with) to only build it if
+    /// the value is a [`Some`].
+    ///
+    /// Available with the `alloc` feature.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is a [`Some`], with a panic message including the
+    /// passed message, and the content of the [`Some`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    ///
+    /// use unwrap_none::UnwrapNone;
+    ///
+    /// let mut squares = HashMap::new();
+    /// for i in -10..=10 {
+    ///     let msg = format!("duplicate key {}", i);
+    ///     squares.insert(i, i * i).expect_none_string(msg);
+    /// }
+    /// ```
+    ///
+    /// ```should_panic
+    /// use std::collections::HashMap;
+    ///
+    /// use unwrap_none::UnwrapNone;
+    ///
+    /// let mut sqrts = HashMap::new();
+    /// for i in -10..=10 {
+    ///     let msg = format!("duplicate key {}", i * i);
+    ///     sqrts.insert(i * i, i).expect_none_string(msg);
+    /// }
+    /// # #[cfg(not(feature = "enforce"))]
+    /// # panic!("the checks are disabled");
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn expect_none_string(self, msg: alloc::string::String)
+    where
+        T: fmt::Debug;
+
+    /// Consumes `self` while expecting [`None`] and returning nothing.
+    ///
+    /// This is like [`expect_none`](UnwrapNone::expect_none), except it takes
+    /// a fixed-capacity [`heapless::String`] as message, which can be built on
+    /// the stack without `alloc`.
+    ///
+    /// Available with the `heapless` feature.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is a [`Some`], with a panic message including the
+    /// passed message, and the content of the [`Some`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::fmt::Write;
+    /// use std::panic;
+    ///
+    /// use unwrap_none::UnwrapNone;
+    ///
+    /// let mut msg = heapless::String::<32>::new();
+    /// write!(msg, "sensor {} already bound", 3).unwrap();
+    /// None::<u8>.expect_none_heapless(&msg);
+    ///
+    /// # #[cfg(feature = "enforce")] {
+    /// let err = panic::catch_unwind(|| Some(0x48).expect_none_heapless(&msg)).unwrap_err();
+    /// # #[cfg(not(feature = "minimal-panic"))]
+    /// assert_eq!(err.downcast_ref::<String>().unwrap(), "sensor 3 already bound: 72");
+    /// # }
+    /// ```
+    #[cfg(feature = "heapless")]
+    fn expect_none_heapless<const N: usize>(self, msg: &heapless::String<N>)
+    where
+        T: fmt::Debug;
+
+    /// Consumes `self` while expecting [`None`] and returning nothing.
+    ///
+    /// This is like [`expect_none`](UnwrapNone::expect_none), except the
+    /// content of the [`Some`] is formatted with [`Display`](fmt::Display)
+    /// instead of [`Debug`](fmt::Debug).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is a [`Some`], with a panic message including the
+    /// passed message, and the content of the [`Some`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fmt;
+    /// use std::panic;
+    ///
+    /// use unwrap_none::UnwrapNone;
+    ///
+    /// struct Celsius(i32);
+    ///
+    /// impl fmt::Display for Celsius {
+    ///     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    ///         write!(f, "{} °C", self.0)
+    ///     }
+    /// }
+    ///
+    /// None::<Celsius>.expect_none_display("temperature already set");
+    ///
+    /// # #[cfg(feature = "enforce")] {
+    /// let err = panic::catch_unwind(|| {
+    ///     Some(Celsius(21)).expect_none_display("temperature already set")
+    /// })
+    /// .unwrap_err();
+    /// let msg = err.downcast_ref::<String>().unwrap();
+    /// assert_eq!(msg, "temperature already set: 21 °C");
+    /// # }
+    /// ```
+    fn expect_none_display(self, msg: &str)
+    where
+        T: fmt::Display;
+
+    /// Consumes `self` while expecting [`None`] and returning nothing.
+    ///
+    /// This is like [`expect_none`](UnwrapNone::expect_none), except the
+    /// content of the [`Some`] is pretty-printed with `{:#?}`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is a [`Some`], with a panic message including the
+    /// passed message, and the content of the [`Some`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::panic;
+    ///
+    /// use unwrap_none::UnwrapNone;
+    ///
+    /// #[derive(Debug)]
+    /// struct Config {
+    ///     name: &'static str,
+    ///     limits: Limits,
+    /// }
+    ///
+    /// #[derive(Debug)]
+    /// struct Limits {
+    ///     min: u32,
+    ///     max: u32,
+    /// }
+    ///
+    /// None::<Config>.expect_none_pretty("config already loaded");
+    ///
+    /// let config = Config { name: "default", limits: Limits { min: 1, max: 10 } };
+    /// # #[cfg(feature = "enforce")] {
+    /// let err = panic::catch_unwind(|| Some(config).expect_none_pretty("config already loaded"))
+    ///     .unwrap_err();
+    /// let msg = err.downcast_ref::<String>().unwrap();
+    /// assert!(msg.starts_with("config already loaded: Config {\n"));
+    /// assert!(msg.contains("\n        max: 10,\n"));
+    /// # }
+    /// ```
+    fn expect_none_pretty(self, msg: &str)
+    where
+        T: fmt::Debug;
+
+    /// Consumes `self` while expecting [`None`] and returning nothing.
+    ///
+    /// This is like [`expect_none`](UnwrapNone::expect_none), except the
+    /// content of the [`Some`] is formatted as configured by `flags`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is a [`Some`], with a panic message including the
+    /// passed message, and the content of the [`Some`] unless
+    /// [`hide_value`](FormatFlags::hide_value) is set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::panic;
+    ///
+    /// use unwrap_none::{FormatFlags, UnwrapNone};
+    ///
+    /// #[derive(Debug)]
+    /// struct Slot(u32);
+    ///
+    /// let styled = |flags| {
+    ///     let err = panic::catch_unwind(|| Some(Slot(7)).expect_none_styled("slot is in use", flags))
+    ///         .unwrap_err();
+    ///     err.downcast_ref::<String>().unwrap().clone()
+    /// };
+    ///
+    /// None::<Slot>.expect_none_styled("slot is in use", FormatFlags::default());
+    ///
+    /// # #[cfg(feature = "enforce")] {
+    /// assert_eq!(styled(FormatFlags::default()), "slot is in use: Slot(7)");
+    /// let alternate = FormatFlags { alternate: true, ..FormatFlags::default() };
+    /// assert_eq!(styled(alternate), "slot is in use: Slot(\n    7,\n)");
+    /// let width = FormatFlags { width: Some(3), ..FormatFlags::default() };
+    /// assert_eq!(styled(width), "slot is in use: Slot(  7)");
+    /// let both = FormatFlags { alternate: true, width: Some(3), ..FormatFlags::default() };
+    /// assert_eq!(styled(both), "slot is in use: Slot(\n      7,\n)");
+    /// let hidden = FormatFlags { hide_value: true, alternate: true, width: Some(3) };
+    /// assert_eq!(styled(hidden), "slot is in use");
+    /// # }
+    /// ```
+    fn expect_none_styled(self, msg: &str, flags: FormatFlags)
+    where
+        T: fmt::Debug;
+
+    /// Returns a [`NoneCheck`], whose panic message can be configured before
+    /// [asserting](NoneCheck::assert) that `self` is [`None`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    ///
+    /// use unwrap_none::UnwrapNone;
+    ///
+    /// let mut users = HashMap::new();
+    /// for (id, name) in [(1, "alice"), (2, "bob")] {
+    ///     users.insert(id, name).none_check().context("importing users").with_key(&id).assert();
+    /// }
+    /// ```
+    fn none_check<'a>(self) -> NoneCheck<'a, T>;
+
+    /// Returns a check expecting `self` to be [`None`], which is performed
+    /// when it is [finished](MustCheckNone::finish) or dropped.
+    ///
+    /// Unlike the methods returning nothing, forgetting to use the result
+    /// triggers the `unused_must_use` lint.
+    ///
+    /// Available with the `std` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use unwrap_none::UnwrapNone;
+    ///
+    /// let check = None::<u32>.check_none();
+    /// // ...
+    /// check.finish();
+    /// ```
+    #[cfg(feature = "std")]
+    fn check_none(self) -> MustCheckNone<T>
+    where
+        T: fmt::Debug;
+
+    /// Consumes `self` while expecting [`None`] and returning nothing, and
+    /// expects this call site to not have run before.
+    ///
+    /// This catches slots that should only be filled once, e.g. during
+    /// initialization, but are touched again by re-entrancy or a second call.
+    /// Call sites are remembered for the lifetime of the process, across all
+    /// threads.
+    ///
+    /// Available with the `std` feature.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this call site ran before, with a panic message including the
+    /// passed message, even if the value is [`None`]. This also happens with
+    /// [`PanicMode::LogOnly`].
+    ///
+    /// Panics if the value is a [`Some`], with a panic message including the
+    /// passed message, and the content of the [`Some`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::panic;
+    ///
+    /// use unwrap_none::UnwrapNone;
+    ///
+    /// fn init(slot: &mut Option<u32>) {
+    ///     slot.take().expect_none_once("already initialized");
+    ///     *slot = Some(1);
+    /// }
+    ///
+    /// let mut first = None;
+    /// init(&mut first);
+    ///
+    /// // The slot is empty, but `init` ran before.
+    /// # #[cfg(feature = "enforce")] {
+    /// let err = panic::catch_unwind(|| init(&mut None)).unwrap_err();
+    /// let msg = err.downcast_ref::<String>().unwrap();
+    /// assert_eq!(msg, "already initialized: called more than once");
+    /// # }
+    /// ```
+    #[cfg(feature = "std")]
+    fn expect_none_once(self, msg: &str)
+    where
+        T: fmt::Debug;
+
+    /// Consumes `self` while expecting [`None`] and returning nothing, and
+    /// with debug assertions enabled, expects no earlier check on this thread
+    /// to have panicked.
+    ///
+    /// This catches test bugs where a failing check is masked, e.g. by a
+    /// [`catch_unwind`](std::panic::catch_unwind) that ignores the panic. Test
+    /// harnesses usually run each test on its own thread, so this only
+    /// considers checks of the same test. Any check of this crate that panics
+    /// is remembered, and the next call to this method on the same thread
+    /// reports it once.
+    ///
+    /// A library cannot observe whether the calling crate is compiled with
+    /// `cfg(test)`, so the stricter check depends on whether this crate is
+    /// compiled with debug assertions instead. That is the case for `cargo
+    /// test` with the default profiles. Without debug assertions, this behaves
+    /// like [`expect_none`](UnwrapNone::expect_none).
+    ///
+    /// Available with the `std` feature.
+    ///
+    /// # Panics
+    ///
+    /// With debug assertions enabled, panics if a check on this thread
+    /// panicked since the last call, with a panic message including the passed
+    /// message. This also happens with [`PanicMode::LogOnly`].
+    ///
+    /// Panics if the value is a [`Some`], with a panic message including the
+    /// passed message, and the content of the [`Some`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::panic;
+    ///
+    /// use unwrap_none::UnwrapNone;
+    ///
+    /// None::<u32>.expect_none_strict("slot is in use");
+    ///
+    /// // A failing check whose panic is swallowed.
+    /// # #[cfg(feature = "enforce")] {
+    /// let _ = panic::catch_unwind(|| Some(1).unwrap_none());
+    ///
+    /// let strict = panic::catch_unwind(|| None::<u32>.expect_none_strict("slot is in use"));
+    /// if cfg!(debug_assertions) {
+    ///     let err = strict.unwrap_err();
+    ///     let msg = err.downcast_ref::<String>().unwrap();
+    ///     assert!(msg.ends_with("a previous check on this thread panicked and was caught"));
+    /// } else {
+    ///     assert!(strict.is_ok());
+    /// }
+    ///
+    /// // The masked failure is only reported once.
+    /// None::<u32>.expect_none_strict("slot is in use");
+    /// # }
+    /// ```
+    #[cfg(feature = "std")]
+    fn expect_none_strict(self, msg: &str)
+    where
+        T: fmt::Debug;
+
+    /// Consumes `self` while expecting [`None`] and returning nothing.
+    ///
+    /// This is like [`expect_none`](UnwrapNone::expect_none), except every
+    /// call is counted in the per-thread [`timed_stats`].
+    ///
+    /// Available with the `timing` feature.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is a [`Some`], with a panic message including the
+    /// passed message, and the content of the [`Some`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use unwrap_none::UnwrapNone;
+    ///
+    /// None::<u32>.expect_none_timed("slot is in use");
+    /// assert_eq!(unwrap_none::timed_stats().calls, 1);
+    /// ```
+    #[cfg(feature = "timing")]
+    fn expect_none_timed(self, msg: &str)
+    where
+        T: fmt::Debug;
+
+    /// Consumes `self` while expecting [`None`] and returning nothing.
+    ///
+    /// This is like [`unwrap_none`](UnwrapNone::unwrap_none), except the
+    /// formatted content of the [`Some`] is cut off after `max_len` bytes and
+    /// followed by `...`. This avoids huge panic messages for huge values.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is a [`Some`], with a panic message including the
+    /// truncated content of the [`Some`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::panic;
+    ///
+    /// use unwrap_none::UnwrapNone;
+    ///
+    /// None::<Vec<u32>>.unwrap_none_truncated(14);
+    ///
+    /// # #[cfg(feature = "enforce")] {
+    /// let err = panic::catch_unwind(|| Some(vec![1, 2, 3]).unwrap_none_truncated(14)).unwrap_err();
+    /// let msg = err.downcast_ref::<String>().unwrap();
+    /// assert!(msg.ends_with(": [1, 2, 3]"));
+    ///
+    /// let err = panic::catch_unwind(|| Some(vec![0; 10_000]).unwrap_none_truncated(14))
+    ///     .unwrap_err();
+    /// let msg = err.downcast_ref::<String>().unwrap();
+    /// assert!(msg.ends_with(": [0, 0, 0, 0, 0..."));
+    /// # }
+    /// ```
+    fn unwrap_none_truncated(self, max_len: usize)
+    where
+        T: fmt::Debug;
+
+    /// Consumes `self` while expecting [`None`] and returning nothing.
+    ///
+    /// This is like [`unwrap_none`](UnwrapNone::unwrap_none), except the panic
+    /// message also includes the [type name](core::any::type_name) of `T`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is a [`Some`], with a panic message including the
+    /// name of `T` and the content of the [`Some`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::panic;
+    ///
+    /// use unwrap_none::UnwrapNone;
+    ///
+    /// #[derive(Debug)]
+    /// struct Point(i32, i32);
+    ///
+    /// None::<Point>.unwrap_none_typed();
+    ///
+    /// # #[cfg(feature = "enforce")] {
+    /// let err = panic::catch_unwind(|| Some(Point(1, 2)).unwrap_none_typed()).unwrap_err();
+    /// let msg = err.downcast_ref::<String>().unwrap();
+    /// assert!(msg.contains("of type "));
+    /// assert!(msg.ends_with("::Point: Point(1, 2)"));
+    /// # }
+    /// ```
+    fn unwrap_none_typed(self)
+    where
+        T: fmt::Debug;
+
+    /// Consumes `self` while expecting [`None`] and returning nothing.
+    ///
+    /// This is like [`unwrap_none_typed`](UnwrapNone::unwrap_none_typed),
+    /// except the value is described by the name `namer` returns for it
+    /// instead of its [`Debug`](fmt::Debug) representation. This keeps the
+    /// panic message short for e.g. large enums, where naming the variant is
+    /// enough. `T` does not need to implement [`Debug`](fmt::Debug).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is a [`Some`], with a panic message including the
+    /// name of `T` and the name returned by `namer`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::panic;
+    ///
+    /// use unwrap_none::UnwrapNone;
+    ///
+    /// enum Frame {
+    ///     Data(Vec<u8>),
+    ///     Ping,
+    /// }
+    ///
+    /// fn frame_name(frame: &Frame) -> &'static str {
+    ///     match frame {
+    ///         Frame::Data(_) => "Data",
+    ///         Frame::Ping => "Ping",
+    ///     }
+    /// }
+    ///
+    /// None::<Frame>.unwrap_none_named(frame_name);
+    ///
+    /// let frame = Frame::Data(vec![0; 1 << 16]);
+    /// # #[cfg(feature = "enforce")] {
+    /// let err = panic::catch_unwind(|| Some(frame).unwrap_none_named(frame_name)).unwrap_err();
+    /// let msg = err.downcast_ref::<String>().unwrap();
+    /// assert!(msg.ends_with("::Frame: Data"));
+    /// # }
+    /// ```
+    fn unwrap_none_named(self, namer: fn(&T) -> &'static str);
+
+    /// Consumes `self` while expecting [`None`] and returning nothing.
+    ///
+    /// This is like [`unwrap_none`](UnwrapNone::unwrap_none), except the panic
+    /// message also includes a [`Backtrace`](std::backtrace::Backtrace) if
+    /// backtraces are enabled with `RUST_BACKTRACE` or `RUST_LIB_BACKTRACE`.
+    ///
+    /// Available with the `std` feature.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is a [`Some`], with a panic message including the
+    /// content of the [`Some`] and a backtrace.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use unwrap_none::UnwrapNone;
+    ///
+    /// None::<i32>.unwrap_none_backtrace();
+    /// ```
+    ///
+    /// ```should_panic
+    /// use unwrap_none::UnwrapNone;
+    ///
+    /// Some(5).unwrap_none_backtrace();
+    /// # #[cfg(not(feature = "enforce"))]
+    /// # panic!("the checks are disabled");
+    /// ```
+    #[cfg(feature = "std")]
+    fn unwrap_none_backtrace(self)
+    where
+        T: fmt::Debug;
+
+    /// Consumes `self` while expecting [`None`] and returning nothing.
+    ///
+    /// Unlike [`unwrap_none`](UnwrapNone::unwrap_none) this panics with the
+    /// content of the [`Some`] itself as payload, using
+    /// [`std::panic::panic_any`]. It can be recovered by downcasting the
+    /// payload returned by [`std::panic::catch_unwind`].
+    ///
+    /// Available with the `std` feature.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is a [`Some`], with the content of the [`Some`] as
+    /// payload.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::panic;
+    ///
+    /// use unwrap_none::UnwrapNone;
+    ///
+    /// None::<i32>.unwrap_none_panic_value();
+    ///
+    /// # #[cfg(feature = "enforce")] {
+    /// let err = panic::catch_unwind(|| Some(5).unwrap_none_panic_value()).unwrap_err();
+    /// assert_eq!(err.downcast::<i32>().ok(), Some(Box::new(5)));
+    /// # }
+    /// ```
+    #[cfg(feature = "std")]
+    fn unwrap_none_panic_value(self)
+    where
+        T: Send + 'static;
+
+    /// Consumes `self` while expecting [`None`] and returning nothing.
+    ///
+    /// Like [`unwrap_none_panic_value`](UnwrapNone::unwrap_none_panic_value)
+    /// this panics with the content of the [`Some`] as payload, but wrapped in
+    /// an [`UnexpectedSome`] along with the caller location. This makes the
+    /// payload easy to tell apart from those of other panics when downcasting
+    /// it.
+    ///
+    /// Since the payload is not a string, the default panic hook prints
+    /// `Box<dyn Any>` instead of the value.
+    ///
+    /// Available with the `std` feature.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is a [`Some`], with an [`UnexpectedSome`] as payload.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::panic;
+    ///
+    /// use unwrap_none::{UnexpectedSome, UnwrapNone};
+    ///
+    /// fn recover<F: FnOnce() + panic::UnwindSafe>(f: F) -> Option<i32> {
+    ///     match panic::catch_unwind(f) {
+    ///         Ok(()) => None,
+    ///         Err(payload) => match payload.downcast::<UnexpectedSome<i32>>() {
+    ///             Ok(unexpected) => Some(unexpected.into_inner()),
+    ///             Err(payload) => panic::resume_unwind(payload),
+    ///         },
+    ///     }
+    /// }
+    ///
+    /// assert_eq!(recover(|| None::<i32>.unwrap_none_typed_panic()), None);
+    /// # #[cfg(feature = "enforce")] {
+    /// assert_eq!(recover(|| Some(5).unwrap_none_typed_panic()), Some(5));
+    /// # }
+    /// ```
+    #[cfg(feature = "std")]
+    fn unwrap_none_typed_panic(self)
+    where
+        T: Send + 'static;
+
+    /// Consumes `self` while expecting [`None`] and returning nothing,
+    /// aborting the process instead of panicking otherwise.
+    ///
+    /// This never unwinds, which makes it suitable for e.g. FFI callbacks.
+    /// Note that no destructors run, not even those of the current thread.
+    ///
+    /// Available with the `std` feature.
+    ///
+    /// # Aborts
+    ///
+    /// Aborts with [`std::process::abort`] if the value is a [`Some`], after
+    /// printing a message including the content of the [`Some`] and the
+    /// location of the caller to stderr.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use unwrap_none::UnwrapNone;
+    ///
+    /// extern "C" fn on_event(pending: *const u32) {
+    ///     // SAFETY: the caller passes either null or a valid pointer.
+    ///     let pending = unsafe { pending.as_ref() };
+    ///     pending.unwrap_none_or_abort();
+    /// }
+    ///
+    /// on_event(std::ptr::null());
+    /// ```
+    #[cfg(feature = "std")]
+    fn unwrap_none_or_abort(self)
+    where
+        T: fmt::Debug;
+
+    /// Consumes `self` while expecting [`None`], printing a message including
+    /// the content of the [`Some`] to stderr otherwise.
+    ///
+    /// This does not panic, and is simpler than setting up a logger with
+    /// [`unwrap_none_or_log`](UnwrapNone::unwrap_none_or_log) for e.g. scripts.
+    /// The message also includes the location of the caller. Nothing is
+    /// printed if the value is [`None`].
+    ///
+    /// Available with the `std` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    ///
+    /// use unwrap_none::UnwrapNone;
+    ///
+    /// let mut seen = HashMap::new();
+    /// for word in ["a", "b", "a"] {
+    ///     // This prints "expected None, got Some(0) at ..." for the second "a".
+    ///     seen.insert(word, 0).unwrap_none_or_eprintln();
+    /// }
+    /// assert_eq!(seen.len(), 2);
+    /// ```
+    #[cfg(feature = "std")]
+    fn unwrap_none_or_eprintln(self)
+    where
+        T: fmt::Debug;
+
+    /// Consumes `self` while expecting [`None`], printing a JSON line
+    /// describing the [`Some`] to stdout otherwise.
+    ///
+    /// The line has the form
+    /// `{"event":"unexpected_some","value":"<debug>","location":"file:line"}`,
+    /// where `<debug>` is the [`Debug`](fmt::Debug) output of the content and
+    /// `location` is the location of the caller, both escaped as JSON
+    /// strings. This is meant for log pipelines that ingest JSON lines
+    /// directly. It does not panic, not even if stdout is closed.
+    ///
+    /// Available with the `json-log` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::env;
+    /// use std::process::Command;
+    ///
+    /// use unwrap_none::UnwrapNone;
+    ///
+    /// // Run the example again as a child process, to capture its stdout.
+    /// if env::var_os("EMIT_JSON").is_some() {
+    ///     None::<&str>.unwrap_none_or_json();
+    ///     Some("say \"hi\"").unwrap_none_or_json();
+    ///     return;
+    /// }
+    ///
+    /// let exe = env::current_exe().unwrap();
+    /// let output = Command::new(exe).env("EMIT_JSON", "1").output().unwrap();
+    /// let stdout = String::from_utf8(output.stdout).unwrap();
+    /// assert_eq!(stdout.lines().count(), 1);
+    ///
+    /// let event: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    /// assert_eq!(event["event"], "unexpected_some");
+    /// assert_eq!(event["value"], r#""say \"hi\"""#);
+    /// assert!(event["location"].as_str().unwrap().contains(".rs:"));
+    /// ```
+    #[cfg(feature = "json-log")]
+    fn unwrap_none_or_json(self)
+    where
+        T: fmt::Debug;
+
+    /// Consumes `self` while expecting [`None`] and returning nothing, with a
+    /// panic message meant to be detected by tools scraping logs.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is a [`Some`], with a panic message of the form
+    /// `unwrap_none: Some({:?})`, i.e. [`EXPECT_NONE_PREFIX`] followed by the
+    /// content of the [`Some`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::panic;
+    ///
+    /// use unwrap_none::{UnwrapNone, EXPECT_NONE_PREFIX};
+    ///
+    /// None::<i32>.unwrap_none_machine();
+    ///
+    /// # #[cfg(feature = "enforce")] {
+    /// let err = panic::catch_unwind(|| Some(5).unwrap_none_machine()).unwrap_err();
+    /// let msg = err.downcast_ref::<String>().unwrap();
+    /// assert!(msg.starts_with(EXPECT_NONE_PREFIX));
+    /// assert_eq!(msg, "unwrap_none: Some(5)");
+    /// # }
+    /// ```
+    fn unwrap_none_machine(self)
+    where
+        T: fmt::Debug;
+}
+
+impl<T> UnwrapNone<T> for Option<T> {
+    #[inline]
+    #[track_caller]
+    fn expect_none(self, msg: &str)
+    where
+        T: MaybeDebug,
+    {
+        #[cfg(not(feature = "minimal-panic"))]
+        if let Some(val) = failed::enforced(self) {
+            failed::expect_none_msg_failed(msg, &val);
+        }
+        #[cfg(feature = "minimal-panic")]
+        if failed::enforced(self).is_some() {
+            failed::none_msg_failed(msg);
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn unwrap_none(self)
+    where
+        T: MaybeDebug,
+    {
+        #[cfg(not(feature = "minimal-panic"))]
+        if let Some(val) = failed::enforced(self) {
+            failed::expect_none_failed(&"called `Option::unwrap_none()` on a `Some` value", &val);
+        }
+        #[cfg(feature = "minimal-panic")]
+        if failed::enforced(self).is_some() {
+            failed::none_failed("called `Option::unwrap_none()` on a `Some` value");
+        }
+    }
+
+    #[inline]
+    fn unwrap_none_notrack(self)
+    where
+        T: MaybeDebug,
+    {
+        #[cfg(not(feature = "minimal-panic"))]
+        if let Some(val) = failed::enforced(self) {
+            failed::expect_none_failed(&"called `Option::unwrap_none()` on a `Some` value", &val);
+        }
+        #[cfg(feature = "minimal-panic")]
+        if failed::enforced(self).is_some() {
+            failed::none_failed("called `Option::unwrap_none()` on a `Some` value");
+        }
+    }
+
+    #[inline]
+    fn unwrap_none_or_else<F>(self, f: F)
+    where
+        F: FnOnce(T),
+    {
+        if let Some(val) = failed::observed(self) {
+            f(val)
+        }
+    }
+
+    #[inline]
+    fn unwrap_none_or_try<E, F>(self, f: F) -> Result<(), E>
+    where
+        F: FnOnce(T) -> Result<(), E>,
+    {
+        match failed::observed(self) {
+            None => Ok(()),
+            Some(val) => f(val),
+        }
+    }
+
+    #[inline]
+    fn for_some<F>(self, f: &mut F)
+    where
+        F: FnMut(T),
+    {
+        if let Some(val) = failed::observed(self) {
+            f(val)
+        }
+    }
+
+    #[inline]
+    fn into_unexpected(self) -> core::option::IntoIter<T> {
+        self.into_iter()
+    }
+
+    #[cfg(feature = "alloc")]
+    #[inline]
+    fn collect_some_into(self, sink: &mut alloc::vec::Vec<T>) {
+        sink.extend(failed::observed(self));
+    }
+
+    #[inline]
+    fn write_some_into<W>(self, w: &mut W, sep: &str) -> fmt::Result
+    where
+        T: fmt::Debug,
+        W: fmt::Write,
+    {
+        match failed::observed(self) {
+            None => Ok(()),
+            Some(val) => failed::write_some(w, sep, &val),
+        }
+    }
+
+    #[inline]
+    fn try_unwrap_none(self) -> Result<(), T> {
+        match failed::observed(self) {
+            None => Ok(()),
+            Some(val) => Err(val),
+        }
+    }
+
+    #[inline]
+    fn none_try(self) -> Result<(), T> {
+        self.try_unwrap_none()
+    }
+
+    #[inline]
+    fn is_none_checked(self) -> (bool, Option<T>) {
+        (self.is_none(), self)
+    }
+
+    #[inline]
+    fn none_or_err(self) -> Option<()> {
+        match failed::observed(self) {
+            None => Some(()),
+            Some(_) => None,
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn expect_none_with<F, D>(self, f: F)
+    where
+        T: fmt::Debug,
+        F: FnOnce() -> D,
+        D: fmt::Display,
+    {
+        if let Some(val) = failed::enforced(self) {
+            failed::expect_none_failed(&f(), &val);
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn expect_none_map<M, F>(self, f: F)
+    where
+        M: fmt::Display,
+        F: FnOnce(&T) -> M,
+    {
+        if let Some(val) = failed::enforced(self) {
+            failed::none_failed_display(&f(&val));
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn expect_none_fmt(self, args: fmt::Arguments<'_>)
+    where
+        T: fmt::Debug,
+    {
+        if let Some(val) = failed::enforced(self) {
+            failed::expect_none_failed(&args, &val);
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn expect_none_msg<D>(self, msg: D)
+    where
+        T: fmt::Debug,
+        D: fmt::Display,
+    {
+        if let Some(val) = failed::enforced(self) {
+            failed::expect_none_failed(&msg, &val);
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn expect_none_to<W>(self, w: &mut W, prefix: &str)
+    where
+        T: fmt::Debug,
+        W: fmt::Write + AsRef<str>,
+    {
+        if let Some(val) = failed::enforced(self) {
+            let written = failed::write_none_failed(w, prefix, &val);
+            failed::expect_none_failed_written(written.map(|()| w.as_ref()), prefix);
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn expect_none_at(self, index: usize, msg: &str)
+    where
+        T: fmt::Debug,
+    {
+        if let Some(val) = failed::enforced(self) {
+            failed::expect_none_at_failed(msg, index, &val);
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn expect_none_for_key<K>(self, key: K, msg: &str)
+    where
+        T: fmt::Debug,
+        K: fmt::Debug,
+    {
+        if let Some(val) = failed::enforced(self) {
+            failed::expect_none_for_key_failed(msg, &key, &val);
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn expect_none_hashed<H>(self, key: &H, msg: &str)
+    where
+        T: fmt::Debug,
+        H: core::hash::Hash + ?Sized,
+    {
+        if let Some(val) = failed::enforced(self) {
+            failed::expect_none_hashed_failed(msg, hash::short_hash(key), &val);
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn expect_none_coded(self, code: &'static str, detail: &str)
+    where
+        T: fmt::Debug,
+    {
+        if let Some(val) = failed::enforced(self) {
+            failed::expect_none_coded_failed(code, detail, &val);
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn expect_absent<F>(self, pred: F, msg: &str)
+    where
+        T: fmt::Debug,
+        F: FnOnce(&T) -> bool,
+    {
+        if let Some(val) = failed::enforced(self.filter(|val| !pred(val))) {
+            failed::expect_none_failed(&msg, &val);
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn expect_none_or_eq(self, sentinel: &T, msg: &str)
+    where
+        T: PartialEq + fmt::Debug,
+    {
+        if let Some(val) = failed::enforced(self.filter(|val| val != sentinel)) {
+            failed::expect_none_failed(&msg, &val);
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn expect_none_or<F>(self, msg: &str, on_some: F)
+    where
+        T: fmt::Debug,
+        F: FnOnce(&T),
+    {
+        if let Some(val) = failed::enforced(self) {
+            on_some(&val);
+            failed::expect_none_failed(&msg, &val);
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn expect_none_and<F>(self, msg: &str, also: F)
+    where
+        T: fmt::Debug,
+        F: FnOnce() -> bool,
+    {
+        if let Some(val) = failed::enforced(self) {
+            failed::expect_none_and_failed(msg, Some(&val));
+        } else if failed::ENFORCE && !also() {
+            failed::expect_none_and_failed(msg, None);
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn expect_none_then<R, F>(self, msg: &str, then: F) -> R
+    where
+        T: fmt::Debug,
+        F: FnOnce() -> R,
+    {
+        if let Some(val) = failed::enforced(self) {
+            failed::expect_none_failed(&msg, &val);
+        }
+        then()
+    }
+
+    #[inline]
+    #[track_caller]
+    fn narrow_none(self, msg: &str) -> IsNone
+    where
+        T: MaybeDebug,
+    {
+        self.expect_none(msg);
+        IsNone::new()
+    }
+
+    #[inline]
+    #[track_caller]
+    fn unwrap_none_redacted(self) {
+        if failed::enforced(self).is_some() {
+            failed::none_failed("called `Option::unwrap_none_redacted()` on a `Some` value");
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn expect_none_exact(self, msg: &str) {
+        if failed::enforced(self).is_some() {
+            failed::none_failed(msg);
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn debug_unwrap_none(self)
+    where
+        T: fmt::Debug,
+    {
+        #[cfg(debug_assertions)]
+        self.unwrap_none();
+    }
+
+    #[inline]
+    unsafe fn unwrap_none_unchecked(self) {
+        if self.is_some() {
+            // SAFETY: the caller must guarantee that `self` is `None`.
+            unsafe { hint::unreachable_unchecked() }
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn assert_none(&self)
+    where
+        T: fmt::Debug,
+    {
+        if let Some(val) = failed::enforced(self.as_ref()) {
+            failed::expect_none_failed(&"called `Option::assert_none()` on a `Some` value", val);
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn expect_none_cloned(&self, msg: &str) -> Option<T>
+    where
+        T: Clone + fmt::Debug,
+    {
+        if let Some(val) = failed::enforced(self.as_ref()) {
+            failed::expect_none_failed(&msg, val);
+        }
+        self.clone()
+    }
+
+    #[inline]
+    #[track_caller]
+    fn take_expect_none(&mut self, msg: &str)
+    where
+        T: fmt::Debug,
+    {
+        self.take().expect_none(msg);
+    }
+
+    #[inline]
+    #[track_caller]
+    fn or_insert_expecting_none<F>(&mut self, f: F) -> &mut T
+    where
+        T: fmt::Debug,
+        F: FnOnce() -> T,
+    {
+        if let Some(val) = failed::enforced(self.as_ref()) {
+            failed::expect_none_failed(
+                &"called `Option::or_insert_expecting_none()` on a `Some` value",
+                val,
+            );
+        }
+        self.insert(f())
+    }
+
+    #[inline]
+    fn none_ok_or<E>(self, err: E) -> Result<(), E> {
+        match failed::observed(self) {
+            None => Ok(()),
+            Some(_) => Err(err),
+        }
+    }
+
+    #[inline]
+    fn none_ok_or_else<E, F>(self, f: F) -> Result<(), E>
+    where
+        F: FnOnce(T) -> E,
+    {
+        match failed::observed(self) {
+            None => Ok(()),
+            Some(val) => Err(f(val)),
+        }
+    }
+
+    #[inline]
+    fn unwrap_none_or_fault(self, code: u32) -> Result<(), u32> {
+        self.none_ok_or(code)
+    }
+
+    #[inline]
+    #[track_caller]
+    fn none_or_location(self) -> Result<(), (&'static core::panic::Location<'static>, T)> {
+        match failed::observed(self) {
+            None => Ok(()),
+            Some(val) => Err((core::panic::Location::caller(), val)),
+        }
+    }
+
+    #[inline]
+    fn inspect_some<F>(self, f: F) -> Option<T>
+    where
+        F: FnOnce(&T),
+    {
+        if let Some(val) = &self {
+            f(val)
+        }
+        self
+    }
+
+    #[inline]
+    fn inspect_none<F>(self, f: F) -> Option<T>
+    where
+        F: FnOnce(),
+    {
+        if self.is_none() {
+            f()
+        }
+        self
+    }
+
+    #[inline]
+    fn map_some_or_default<U, F>(self, f: F) -> U
+    where
+        U: Default,
+        F: FnOnce(T) -> U,
+    {
+        match failed::observed(self) {
+            None => U::default(),
+            Some(val) => f(val),
+        }
+    }
+
+    #[inline]
+    fn map_or_none<U, F>(self, default: U, f: F) -> U
+    where
+        F: FnOnce(T) -> U,
+    {
+        match failed::observed(self) {
+            None => default,
+            Some(val) => f(val),
+        }
+    }
+
+    #[inline]
+    fn map_or_else_none<U, D, F>(self, default: D, f: F) -> U
+    where
+        D: FnOnce() -> U,
+        F: FnOnce(T) -> U,
+    {
+        match failed::observed(self) {
+            None => default(),
+            Some(val) => f(val),
+        }
+    }
+
+    #[inline]
+    fn unwrap_none_or_break<B, F>(self, f: F) -> ControlFlow<B>
+    where
+        F: FnOnce(T) -> B,
+    {
+        match failed::observed(self) {
+            None => ControlFlow::Continue(()),
+            Some(val) => ControlFlow::Break(f(val)),
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[inline]
+    fn try_unwrap_none_err(self) -> Result<(), UnwrapNoneError<T>> {
+        self.try_unwrap_none().map_err(UnwrapNoneError::new)
+    }
+
+    #[cfg(feature = "miette")]
+    #[inline]
+    fn try_unwrap_none_diagnostic(self) -> miette::Result<()>
+    where
+        T: fmt::Debug,
+    {
+        match failed::observed(self) {
+            None => Ok(()),
+            Some(val) => Err(UnwrapNoneDiagnostic::new(&val).into()),
+        }
+    }
+
+    #[cfg(feature = "anyhow")]
+    #[inline]
+    fn try_unwrap_none_anyhow(self) -> anyhow::Result<()>
+    where
+        T: fmt::Debug,
+    {
+        match failed::observed(self) {
+            None => Ok(()),
+            Some(val) => Err(anyhow::anyhow!("expected None, got Some({:?})", val)),
+        }
+    }
+
+    #[cfg(feature = "color-eyre")]
+    #[inline]
+    fn try_unwrap_none_eyre(self) -> color_eyre::Result<()>
+    where
+        T: fmt::Debug,
+    {
+        use color_eyre::Section;
+
+        match failed::observed(self) {
+            None => Ok(()),
+            Some(val) => Err(
+                color_eyre::eyre::eyre!("expected None, got Some({:?})", val)
+                    .suggestion("make sure the slot is empty before filling it"),
+            ),
+        }
+    }
+
+    #[cfg(feature = "syn")]
+    #[inline]
+    fn try_unwrap_none_spanned<S>(self, span: S) -> Result<(), syn::Error>
+    where
+        T: fmt::Debug,
+        S: Into<proc_macro2::Span>,
+    {
+        match failed::observed(self) {
+            None => Ok(()),
+            Some(val) => Err(syn::Error::new(
+                span.into(),
+                format_args!("expected None, got Some({:?})", val),
+            )),
+        }
+    }
+
+    #[cfg(feature = "log")]
+    #[inline]
+    fn unwrap_none_or_log(self)
+    where
+        T: fmt::Debug,
+    {
+        self.unwrap_none_or_log_target(env!("CARGO_CRATE_NAME"));
+    }
+
+    #[cfg(feature = "log")]
+    #[inline]
+    fn unwrap_none_or_log_target(self, target: &str)
+    where
+        T: fmt::Debug,
+    {
+        if let Some(val) = failed::observed(self) {
+            logging::log_unexpected(target, &val);
+        }
+    }
+
+    #[cfg(feature = "log")]
+    #[inline]
+    #[track_caller]
+    fn unwrap_none_or_warn_once(self)
+    where
+        T: fmt::Debug,
+    {
+        if let Some(val) = failed::observed(self) {
+            logging::warn_unexpected_once(&val, core::panic::Location::caller());
+        }
+    }
+
+    #[cfg(feature = "tracing")]
+    #[inline]
+    #[track_caller]
+    fn unwrap_none_or_trace(self)
+    where
+        T: fmt::Debug,
+    {
+        if let Some(val) = failed::observed(self) {
+            trace::trace_unexpected(&val, core::panic::Location::caller());
+        }
+    }
+
+    #[cfg(feature = "sentry")]
+    #[inline]
+    #[track_caller]
+    fn unwrap_none_or_capture(self)
+    where
+        T: fmt::Debug,
+    {
+        if let Some(val) = failed::observed(self) {
+            capture::capture_unexpected(&val, core::panic::Location::caller());
+        }
+    }
+
+    #[cfg(feature = "defmt")]
+    #[inline]
+    fn unwrap_none_defmt(self)
+    where
+        T: defmt::Format,
+    {
+        if let Some(val) = failed::enforced(self) {
+            defmt::panic!(
+                "called `Option::unwrap_none_defmt()` on a `Some` value: {}",
+                val
+            );
+        }
+    }
+
+    #[cfg(feature = "ufmt")]
+    #[inline]
+    #[track_caller]
+    fn unwrap_none_ufmt(self)
+    where
+        T: ufmt::uDebug,
+    {
+        if let Some(val) = failed::enforced(self) {
+            ufmt_panic::unwrap_none_failed_ufmt(&val);
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    #[inline]
+    #[track_caller]
+    fn expect_none_string(self, msg: alloc::string::String)
+    where
+        T: fmt::Debug,
+    {
+        self.expect_none(&msg);
+    }
+
+    #[cfg(feature = "heapless")]
+    #[inline]
+    #[track_caller]
+    fn expect_none_heapless<const N: usize>(self, msg: &heapless::String<N>)
+    where
+        T: fmt::Debug,
+    {
+        self.expect_none(msg);
+    }
+
+    #[inline]
+    #[track_caller]
+    fn expect_none_display(self, msg: &str)
+    where
+        T: fmt::Display,
+    {
+        if let Some(val) = failed::enforced(self) {
+            failed::expect_none_failed_display(msg, &val);
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn expect_none_pretty(self, msg: &str)
+    where
+        T: fmt::Debug,
+    {
+        if let Some(val) = failed::enforced(self) {
+            failed::expect_none_failed_pretty(msg, &val);
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn expect_none_styled(self, msg: &str, flags: FormatFlags)
+    where
+        T: fmt::Debug,
+    {
+        if let Some(val) = failed::enforced(self) {
+            failed::expect_none_failed_styled(msg, &val, flags);
+        }
+    }
+
+    #[inline]
+    fn none_check<'a>(self) -> NoneCheck<'a, T> {
+        NoneCheck::new(self)
+    }
+
+    #[cfg(feature = "std")]
+    #[inline]
+    #[track_caller]
+    fn check_none(self) -> MustCheckNone<T>
+    where
+        T: fmt::Debug,
+    {
+        MustCheckNone::new(self)
+    }
+
+    #[cfg(feature = "std")]
+    #[inline]
+    #[track_caller]
+    fn expect_none_once(self, msg: &str)
+    where
+        T: fmt::Debug,
+    {
+        if failed::ENFORCE && !once::first_call(core::panic::Location::caller()) {
+            failed::expect_none_once_failed(msg);
+        }
+        if let Some(val) = failed::enforced(self) {
+            failed::expect_none_failed(&msg, &val);
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[inline]
+    #[track_caller]
+    fn expect_none_strict(self, msg: &str)
+    where
+        T: fmt::Debug,
+    {
+        if failed::ENFORCE && cfg!(debug_assertions) && strict::take_failure() {
+            failed::expect_none_strict_failed(msg);
+        }
+        if let Some(val) = failed::enforced(self) {
+            failed::expect_none_failed(&msg, &val);
+        }
+    }
+
+    #[cfg(feature = "timing")]
+    #[inline]
+    #[track_caller]
+    fn expect_none_timed(self, msg: &str)
+    where
+        T: fmt::Debug,
+    {
+        timing::record(self.is_some());
+        if let Some(val) = failed::enforced(self) {
+            failed::expect_none_failed(&msg, &val);
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn unwrap_none_truncated(self, max_len: usize)
+    where
+        T: fmt::Debug,
+    {
+        if let Some(val) = failed::enforced(self) {
+            failed::unwrap_none_failed_truncated(&val, max_len);
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn unwrap_none_typed(self)
+    where
+        T: fmt::Debug,
+    {
+        if let Some(val) = failed::enforced(self) {
+            failed::unwrap_none_failed_typed(core::any::type_name::<T>(), &val);
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn unwrap_none_named(self, namer: fn(&T) -> &'static str) {
+        if let Some(val) = failed::enforced(self) {
+            failed::unwrap_none_failed_named(core::any::type_name::<T>(), namer(&val));
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[inline]
+    #[track_caller]
+    fn unwrap_none_backtrace(self)
+    where
+        T: fmt::Debug,
+    {
+        if let Some(val) = failed::enforced(self) {
+            failed::unwrap_none_failed_backtrace(&val);
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[inline]
+    #[track_caller]
+    fn unwrap_none_panic_value(self)
+    where
+        T: Send + 'static,
+    {
+        if let Some(val) = failed::enforced(self) {
+            std::panic::panic_any(val);
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[inline]
+    #[track_caller]
+    fn unwrap_none_typed_panic(self)
+    where
+        T: Send + 'static,
+    {
+        if let Some(val) = failed::enforced(self) {
+            std::panic::panic_any(UnexpectedSome::new(val, core::panic::Location::caller()));
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[inline]
+    #[track_caller]
+    fn unwrap_none_or_abort(self)
+    where
+        T: fmt::Debug,
+    {
+        if let Some(val) = failed::enforced(self) {
+            failed::unwrap_none_failed_abort(&val);
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[inline]
+    #[track_caller]
+    fn unwrap_none_or_eprintln(self)
+    where
+        T: fmt::Debug,
+    {
+        if let Some(val) = failed::observed(self) {
+            failed::eprint_unexpected(&val);
+        }
+    }
+
+    #[cfg(feature = "json-log")]
+    #[inline]
+    #[track_caller]
+    fn unwrap_none_or_json(self)
+    where
+        T: fmt::Debug,
+    {
+        if let Some(val) = failed::observed(self) {
+            json::emit_unexpected(&val, core::panic::Location::caller());
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn unwrap_none_machine(self)
+    where
+        T: fmt::Debug,
+    {
+        if let Some(val) = failed::enforced(self) {
+            failed::unwrap_none_failed_machine(&val);
+        }
+    }
+}
+
+/// Expects `opt` to be [`None`] in `const` contexts.
+///
+/// Trait methods cannot be called in `const` contexts, so this is a free
+/// function instead. The content of the [`Some`] cannot be formatted in
+/// `const` contexts either, so it is excluded from the panic message.
+///
+/// # Panics
+///
+/// Panics if `opt` is a [`Some`]. If evaluated at compile time this results in
+/// a compile error.
+///
+/// # Examples
+///
+/// ```
+/// use unwrap_none::const_unwrap_none;
+///
+/// const OVERRIDE: Option<u8> = None;
+///
+/// const { const_unwrap_none(OVERRIDE) };
+/// ```
+///
+/// ```compile_fail,E0080
+/// use unwrap_none::const_unwrap_none;
+///
+/// const OVERRIDE: Option<u8> = Some(1);
+///
+/// // This fails to compile, since `OVERRIDE` is a `Some`.
+/// const { const_unwrap_none(OVERRIDE) };
+/// # #[cfg(not(feature = "enforce"))]
+/// # const _: () = panic!("the checks are disabled");
+/// ```
+#[inline]
+#[track_caller]
+pub const fn const_unwrap_none<T>(opt: Option<T>)
+where
+    T: Copy,
+{
+    if macros::ENFORCE && opt.is_some() {
+        panic!("called `const_unwrap_none()` on a `Some` value");
+    }
+}
+
+/// Expects `opt` to be [`None`].
+///
+/// This is [`UnwrapNone::expect_none`] as a free function, for code that
+/// cannot import the trait, such as macro-generated code. Besides options, it
+/// accepts any type implementing [`IntoOption`].
+///
+/// # Panics
+///
+/// Panics if `opt` is a [`Some`], with a panic message including the passed
+/// message, and the content of the [`Some`].
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// let mut squares = HashMap::new();
+/// for i in -10..=10 {
+///     unwrap_none::expect_none(squares.insert(i, i * i), "duplicate key");
+/// }
+/// ```
+///
+/// ```should_panic
+/// unwrap_none::expect_none(Some(1), "slot is occupied");
+/// # #[cfg(not(feature = "enforce"))]
+/// # panic!("the checks are disabled");
+/// ```
+#[inline]
+#[track_caller]
+pub fn expect_none<T>(opt: impl IntoOption<T>, msg: &str)
+where
+    T: MaybeDebug,
+{
+    opt.into_option().expect_none(msg);
+}
+
+/// Expects `opt` to be [`None`].
+///
+/// This is [`UnwrapNone::unwrap_none`] as a free function, for code that
+/// cannot import the trait, such as macro-generated code. Besides options, it
+/// accepts any type implementing [`IntoOption`].
+///
+/// # Panics
+///
+/// Panics if `opt` is a [`Some`], with a panic message including the content
+/// of the [`Some`].
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashMap;
+/// use std::panic;
+///
+/// let mut squares = HashMap::new();
+/// for i in -10..=10 {
+///     unwrap_none::unwrap_none(squares.insert(i, i * i));
+/// }
+///
+/// # #[cfg(feature = "enforce")] {
+/// let err = panic::catch_unwind(|| unwrap_none::unwrap_none(Some(1))).unwrap_err();
+/// let msg = err.downcast_ref::<String>().unwrap();
+/// # #[cfg(not(feature = "minimal-panic"))]
+/// assert_eq!(msg, "called `Option::unwrap_none()` on a `Some` value: 1");
+/// # }
+/// ```
+#[inline]
+#[track_caller]
+pub fn unwrap_none<T>(opt: impl IntoOption<T>)
+where
+    T: MaybeDebug,
+{
+    opt.into_option().unwrap_none();
 }
@@ -0,0 +1,53 @@
+use core::fmt;
+use core::panic::Location;
+use core::ptr;
+use core::sync::atomic::{AtomicPtr, Ordering};
+
+/// The amount of call sites `warn_unexpected_once` remembers.
+const SEEN_CAPACITY: usize = 64;
+
+// Fixed size and lock-free so it works without `std`.
+static SEEN: [AtomicPtr<Location<'static>>; SEEN_CAPACITY] =
+    [const { AtomicPtr::new(ptr::null_mut()) }; SEEN_CAPACITY];
+
+#[inline(never)]
+#[cold]
+pub(crate) fn log_unexpected(target: &str, value: &dyn fmt::Debug) {
+    log::error!(target: target, "expected None, got Some({:?})", value);
+}
+
+#[inline(never)]
+#[cold]
+pub(crate) fn warn_unexpected_once(value: &dyn fmt::Debug, location: &'static Location<'static>) {
+    if first_at(location) {
+        log::warn!(
+            target: env!("CARGO_CRATE_NAME"),
+            "expected None, got Some({:?}) at {}",
+            value,
+            location
+        );
+    }
+}
+
+/// Remembers `location`, returning whether it wasn't seen before.
+///
+/// Locations are compared by value, as the same call site is not guaranteed
+/// to always pass the same reference.
+fn first_at(location: &'static Location<'static>) -> bool {
+    let new = location as *const Location<'static> as *mut Location<'static>;
+    for slot in &SEEN {
+        let mut current = slot.load(Ordering::Acquire);
+        if current.is_null() {
+            match slot.compare_exchange(current, new, Ordering::AcqRel, Ordering::Acquire) {
+                Ok(_) => return true,
+                Err(other) => current = other,
+            }
+        }
+        // SAFETY: only pointers derived from `&'static Location` are stored.
+        if unsafe { *current } == *location {
+            return false;
+        }
+    }
+    // Out of space, so don't suppress anything.
+    true
+}
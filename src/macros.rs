@@ -0,0 +1,701 @@
+use core::fmt;
+
+use crate::failed::panic_in_context;
+
+/// Whether the checks are enabled, i.e. whether the `enforce` feature is.
+#[doc(hidden)]
+pub const ENFORCE: bool = cfg!(feature = "enforce");
+
+/// Whether debug assertions were enabled when compiling this crate.
+///
+/// Doc tests are always compiled with debug assertions, so this is how they
+/// can tell whether e.g. [`debug_unwrap_none`](crate::UnwrapNone::debug_unwrap_none)
+/// checks anything.
+#[doc(hidden)]
+pub const DEBUG_ASSERTIONS: bool = cfg!(debug_assertions);
+
+/// Returns `opt`, or [`None`] if the checks are disabled.
+///
+/// Every check goes through this, so disabling the `enforce` feature turns
+/// them into no-ops while still consuming the value. With the `std` feature,
+/// this also returns [`None`] after logging a [`Some`] if the panic mode is
+/// [`PanicMode::LogOnly`](crate::PanicMode::LogOnly).
+#[doc(hidden)]
+#[inline(always)]
+#[track_caller]
+pub fn enforced<T>(opt: Option<T>) -> Option<T> {
+    let opt = observed(opt);
+    if !ENFORCE {
+        return None;
+    }
+    #[cfg(feature = "std")]
+    if opt.is_some() && crate::mode::log_only(core::any::type_name::<T>()) {
+        return None;
+    }
+    opt
+}
+
+/// Returns `opt`, counting it with the `metrics` feature if it is a [`Some`].
+///
+/// Every method observing an unexpected value goes through this, either
+/// directly or through [`enforced`].
+#[inline(always)]
+pub(crate) fn observed<T>(opt: Option<T>) -> Option<T> {
+    #[cfg(feature = "metrics")]
+    if opt.is_some() {
+        crate::metrics::record_unexpected_some();
+    }
+    opt
+}
+
+/// Expects an expression to evaluate to [`None`].
+///
+/// This is like [`UnwrapNone::unwrap_none`](crate::UnwrapNone::unwrap_none),
+/// except the panic message also includes the expression itself.
+///
+/// # Panics
+///
+/// Panics if the expression evaluates to a [`Some`], with a panic message
+/// including the expression and the content of the [`Some`].
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// use unwrap_none::unwrap_none;
+///
+/// let mut squares = HashMap::new();
+/// for i in -10..=10 {
+///     // This will not panic, since all keys are unique.
+///     unwrap_none!(squares.insert(i, i * i));
+/// }
+/// ```
+///
+/// ```should_panic
+/// use std::collections::HashMap;
+///
+/// use unwrap_none::unwrap_none;
+///
+/// let mut sqrts = HashMap::new();
+/// for i in -10..=10 {
+///     // This will panic with "expected `sqrts.insert(i * i, i)` to be None,
+///     // got Some(-1)".
+///     unwrap_none!(sqrts.insert(i * i, i));
+/// }
+/// # #[cfg(not(feature = "enforce"))]
+/// # panic!("the checks are disabled");
+/// ```
+#[macro_export]
+macro_rules! unwrap_none {
+    ($expr:expr $(,)?) => {
+        $crate::macros::unwrap_none_expr($expr, ::core::stringify!($expr))
+    };
+}
+
+/// Expects several expressions to evaluate to [`None`].
+///
+/// The expressions are evaluated in order, from left to right. Evaluation
+/// stops at the first expression that evaluates to a [`Some`], i.e. any
+/// expressions after it are not evaluated.
+///
+/// # Panics
+///
+/// Panics if any expression evaluates to a [`Some`], with a panic message
+/// including the index of the argument, the expression and the content of the
+/// [`Some`].
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// use unwrap_none::expect_all_none;
+///
+/// let mut map = HashMap::new();
+/// expect_all_none!(map.insert(1, "a"), map.insert(2, "b"), map.insert(3, "c"));
+/// ```
+///
+/// ```
+/// use std::collections::HashMap;
+/// use std::panic::{self, AssertUnwindSafe};
+///
+/// use unwrap_none::expect_all_none;
+///
+/// # #[cfg(feature = "enforce")] {
+/// let mut map = HashMap::new();
+/// let err = panic::catch_unwind(AssertUnwindSafe(|| {
+///     expect_all_none!(map.insert(1, "a"), map.insert(1, "b"), map.insert(3, "c"));
+/// }))
+/// .unwrap_err();
+///
+/// let msg = err.downcast_ref::<String>().unwrap();
+/// assert_eq!(
+///     msg,
+///     "expected argument 1 (`map.insert(1, \"b\")`) to be None, got Some(\"a\")",
+/// );
+/// // The last expression was not evaluated.
+/// assert!(!map.contains_key(&3));
+/// # }
+/// ```
+#[macro_export]
+macro_rules! expect_all_none {
+    ($($expr:expr),+ $(,)?) => {{
+        let mut index = 0usize;
+        $(
+            $crate::macros::expect_all_none_expr($expr, index, ::core::stringify!($expr));
+            index += 1;
+        )+
+        let _ = index;
+    }};
+}
+
+/// Asserts that an expression evaluates to [`None`].
+///
+/// Like the [`assert!`] family of macros, this does not consume the value and
+/// accepts an optional custom message with format arguments. The message is
+/// only formatted if the assertion fails.
+///
+/// # Panics
+///
+/// Panics if the expression evaluates to a [`Some`]. Like [`assert_eq!`], the
+/// panic message includes the expression and the custom message, if any,
+/// followed by the expected and actual value on separate lines.
+///
+/// # Examples
+///
+/// ```
+/// use unwrap_none::assert_none;
+///
+/// let pending: Option<u32> = None;
+/// assert_none!(pending);
+/// assert_none!(pending, "request {} is still pending", 3);
+/// ```
+///
+/// ```should_panic
+/// use unwrap_none::assert_none;
+///
+/// let pending = Some(7);
+/// // This will panic with "assertion `pending is None` failed", followed by
+/// // "expected: None" and "actual: Some(7)" on separate lines.
+/// assert_none!(pending);
+/// # #[cfg(not(feature = "enforce"))]
+/// # panic!("the checks are disabled");
+/// ```
+///
+/// ```
+/// use std::panic;
+///
+/// use unwrap_none::assert_none;
+///
+/// let pending = Some(7);
+/// # #[cfg(feature = "enforce")] {
+/// let err = panic::catch_unwind(|| assert_none!(pending, "request {} is still pending", 3))
+///     .unwrap_err();
+/// assert_eq!(
+///     err.downcast_ref::<String>().unwrap(),
+///     "assertion `pending is None` failed: request 3 is still pending\n\
+///      expected: None\n  \
+///      actual: Some(7)",
+/// );
+/// # }
+/// ```
+#[macro_export]
+macro_rules! assert_none {
+    ($expr:expr $(,)?) => {
+        if let ::core::option::Option::Some(val) =
+            $crate::macros::enforced(::core::option::Option::as_ref(&$expr))
+        {
+            $crate::macros::assert_none_failed(
+                ::core::stringify!($expr),
+                val,
+                ::core::option::Option::None,
+            );
+        }
+    };
+    ($expr:expr, $($arg:tt)+) => {
+        if let ::core::option::Option::Some(val) =
+            $crate::macros::enforced(::core::option::Option::as_ref(&$expr))
+        {
+            $crate::macros::assert_none_failed(
+                ::core::stringify!($expr),
+                val,
+                ::core::option::Option::Some(::core::format_args!($($arg)+)),
+            );
+        }
+    };
+}
+
+/// Asserts that a call returns [`None`].
+///
+/// The call is evaluated exactly once, so side effects like
+/// [`HashMap::insert`] are not repeated when building the panic message.
+///
+/// [`HashMap::insert`]: https://doc.rust-lang.org/std/collections/struct.HashMap.html#method.insert
+///
+/// # Panics
+///
+/// Panics if the call returns a [`Some`], with a panic message including the
+/// call and the content of the [`Some`].
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// use unwrap_none::assert_none_returns;
+///
+/// let mut map = HashMap::new();
+/// assert_none_returns!(map.insert(1, "one"));
+/// ```
+///
+/// ```
+/// use std::cell::Cell;
+/// use std::panic::{self, AssertUnwindSafe};
+///
+/// use unwrap_none::assert_none_returns;
+///
+/// let calls = Cell::new(0);
+/// let next_id = || {
+///     calls.set(calls.get() + 1);
+///     Some(calls.get())
+/// };
+/// # #[cfg(feature = "enforce")] {
+/// let err = panic::catch_unwind(AssertUnwindSafe(|| assert_none_returns!(next_id()))).unwrap_err();
+/// assert_eq!(
+///     err.downcast_ref::<String>().unwrap(),
+///     "expected `next_id()` to return None, got Some(1)",
+/// );
+/// assert_eq!(calls.get(), 1);
+/// # }
+/// ```
+#[macro_export]
+macro_rules! assert_none_returns {
+    ($call:expr $(,)?) => {
+        match $call {
+            ret => $crate::macros::assert_none_returns_expr(ret, ::core::stringify!($call)),
+        }
+    };
+}
+
+/// Asserts that an expression evaluates to [`None`], or to a [`Some`] whose
+/// content matches a pattern.
+///
+/// This generalizes
+/// [`expect_none_or_eq`](crate::UnwrapNone::expect_none_or_eq) to arbitrary
+/// patterns, for slots that should be empty unless they hold an allowed
+/// value. Like [`matches!`], the pattern may be followed by an `if` guard.
+/// The pattern is matched first, so a [`Some`] matching it is neither counted
+/// nor logged.
+///
+/// # Panics
+///
+/// Panics if the expression evaluates to a [`Some`] that does not match the
+/// pattern, with a panic message including the expression, the pattern and
+/// the content of the [`Some`].
+///
+/// # Examples
+///
+/// ```
+/// use unwrap_none::assert_none_matches;
+///
+/// #[derive(Debug)]
+/// enum Slot {
+///     Reserved,
+///     Taken(u32),
+/// }
+///
+/// assert_none_matches!(None::<Slot>, Slot::Reserved);
+/// assert_none_matches!(Some(Slot::Reserved), Slot::Reserved);
+/// assert_none_matches!(Some(Slot::Taken(0)), Slot::Taken(id) if id == 0);
+/// # #[cfg(feature = "metrics")]
+/// # assert_eq!(unwrap_none::unexpected_some_count(), 0);
+/// ```
+///
+/// ```should_panic
+/// use unwrap_none::assert_none_matches;
+///
+/// // This will panic, since only odd values are allowed.
+/// assert_none_matches!(Some(4), n if n % 2 == 1);
+/// # #[cfg(not(feature = "enforce"))]
+/// # panic!("the checks are disabled");
+/// ```
+///
+/// ```
+/// use std::panic;
+///
+/// use unwrap_none::assert_none_matches;
+///
+/// let slot = Some("taken");
+/// # #[cfg(feature = "enforce")] {
+/// let err = panic::catch_unwind(|| assert_none_matches!(slot, "reserved")).unwrap_err();
+/// assert_eq!(
+///     err.downcast_ref::<String>().unwrap(),
+///     "expected `slot` to be None or match `\"reserved\"`, got Some(\"taken\")",
+/// );
+/// # }
+/// ```
+#[macro_export]
+macro_rules! assert_none_matches {
+    ($expr:expr, $pat:pat $(if $guard:expr)? $(,)?) => {
+        if let ::core::option::Option::Some(val) = $expr {
+            match val {
+                $pat $(if $guard)? => {}
+                val => {
+                    if let ::core::option::Option::Some(val) =
+                        $crate::macros::enforced(::core::option::Option::Some(val))
+                    {
+                        $crate::macros::assert_none_matches_failed(
+                            ::core::stringify!($expr),
+                            ::core::stringify!($pat),
+                            &val,
+                        )
+                    }
+                }
+            }
+        }
+    };
+}
+
+/// Asserts at compile time that a constant [`Option`] is [`None`].
+///
+/// This expands to an anonymous `const` item, so it can be used both at module
+/// level and inside functions. Like
+/// [`const_unwrap_none`](crate::const_unwrap_none), the content of the
+/// [`Some`] cannot be formatted, but the error includes the expression.
+///
+/// # Examples
+///
+/// ```
+/// use unwrap_none::const_assert_none;
+///
+/// const OVERRIDE: Option<u8> = None;
+/// const_assert_none!(OVERRIDE);
+///
+/// const LIMITS: [Option<u32>; 2] = [None, None];
+/// const_assert_none!(LIMITS[1]);
+/// ```
+///
+/// ```compile_fail,E0080
+/// use unwrap_none::const_assert_none;
+///
+/// const OVERRIDE: Option<u8> = Some(1);
+/// // This fails to compile with "expected `OVERRIDE` to be None".
+/// const_assert_none!(OVERRIDE);
+/// # #[cfg(not(feature = "enforce"))]
+/// # const _: () = panic!("the checks are disabled");
+/// ```
+#[macro_export]
+macro_rules! const_assert_none {
+    ($expr:expr $(,)?) => {
+        const _: () = if $crate::macros::ENFORCE && ::core::option::Option::is_some(&$expr) {
+            ::core::panic!(::core::concat!(
+                "expected `",
+                ::core::stringify!($expr),
+                "` to be None"
+            ));
+        };
+    };
+}
+
+/// Returns from the enclosing function if an expression evaluates to a
+/// [`Some`].
+///
+/// The expression is evaluated exactly once. If it is a [`Some`], the value is
+/// dropped and the function returns the second argument, which is only
+/// evaluated then. Without a second argument, the function returns `()`.
+///
+/// This is for functions that cannot use `?` on
+/// [`try_unwrap_none`](crate::UnwrapNone::try_unwrap_none), since they do not
+/// return a [`Result`].
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// use unwrap_none::return_if_some;
+///
+/// fn insert_all(map: &mut HashMap<u32, u32>, values: &[u32]) -> u32 {
+///     let mut inserted = 0;
+///     for &i in values {
+///         return_if_some!(map.insert(i, i), inserted);
+///         inserted += 1;
+///     }
+///     inserted
+/// }
+///
+/// let mut map = HashMap::new();
+/// assert_eq!(insert_all(&mut map, &[1, 2, 3]), 3);
+/// assert_eq!(insert_all(&mut map, &[4, 5, 1, 6]), 2);
+/// assert!(!map.contains_key(&6));
+/// ```
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// use unwrap_none::return_if_some;
+///
+/// fn register(map: &mut HashMap<u32, ()>, log: &mut Vec<u32>, id: u32) {
+///     return_if_some!(map.insert(id, ()));
+///     log.push(id);
+/// }
+///
+/// let mut map = HashMap::new();
+/// let mut log = Vec::new();
+/// register(&mut map, &mut log, 7);
+/// register(&mut map, &mut log, 7);
+/// assert_eq!(log, [7]);
+/// ```
+#[macro_export]
+macro_rules! return_if_some {
+    ($expr:expr $(,)?) => {
+        $crate::return_if_some!($expr, ())
+    };
+    ($expr:expr, $ret:expr $(,)?) => {
+        if $crate::macros::is_unexpected($expr) {
+            return $ret;
+        }
+    };
+}
+
+/// Defines module-local `unwrap_none` and `expect_none` functions, which can
+/// be turned off for just that module.
+///
+/// The functions behave like the free [`unwrap_none`](crate::unwrap_none())
+/// and [`expect_none`](crate::expect_none()) if `enabled` is `true`. If it is
+/// `false`, they do nothing beyond dropping the value, so the checks can be
+/// turned off in e.g. a hot inner module while staying on everywhere else.
+/// `enabled` can be any constant expression, such as a `cfg!`.
+///
+/// The functions are private to the module, but can be used by its child
+/// modules.
+///
+/// # Examples
+///
+/// ```
+/// use std::panic;
+///
+/// mod checked {
+///     unwrap_none::none_asserts! { enabled = true; }
+///
+///     pub fn insert(slot: &mut Option<u32>, value: u32) {
+///         unwrap_none(slot.replace(value));
+///     }
+/// }
+///
+/// mod hot {
+///     unwrap_none::none_asserts! { enabled = false; }
+///
+///     pub fn insert(slot: &mut Option<u32>, value: u32) {
+///         expect_none(slot.replace(value), "slot is taken");
+///     }
+/// }
+///
+/// let mut slot = Some(1);
+/// hot::insert(&mut slot, 2);
+/// assert_eq!(slot, Some(2));
+///
+/// # #[cfg(feature = "enforce")] {
+/// assert!(panic::catch_unwind(move || checked::insert(&mut slot, 3)).is_err());
+/// # }
+/// ```
+#[macro_export]
+macro_rules! none_asserts {
+    (enabled = $enabled:expr $(;)?) => {
+        #[allow(dead_code)]
+        #[inline]
+        #[track_caller]
+        fn unwrap_none<T>(opt: impl $crate::IntoOption<T>)
+        where
+            T: $crate::MaybeDebug,
+        {
+            const ENABLED: bool = $enabled;
+            if ENABLED {
+                $crate::unwrap_none(opt);
+            }
+        }
+
+        #[allow(dead_code)]
+        #[inline]
+        #[track_caller]
+        fn expect_none<T>(opt: impl $crate::IntoOption<T>, msg: &str)
+        where
+            T: $crate::MaybeDebug,
+        {
+            const ENABLED: bool = $enabled;
+            if ENABLED {
+                $crate::expect_none(opt, msg);
+            }
+        }
+    };
+}
+
+#[doc(hidden)]
+#[inline]
+#[track_caller]
+pub fn unwrap_none_expr<T>(opt: Option<T>, expr: &str)
+where
+    T: fmt::Debug,
+{
+    if let Some(val) = enforced(opt) {
+        unwrap_none_expr_failed(expr, &val);
+    }
+}
+
+#[inline(never)]
+#[cold]
+#[track_caller]
+fn unwrap_none_expr_failed(expr: &str, value: &dyn fmt::Debug) -> ! {
+    #[cfg(feature = "std")]
+    crate::hook::on_unexpected_some(value);
+    panic_in_context!("expected `{}` to be None, got Some({:?})", expr, value)
+}
+
+#[doc(hidden)]
+#[inline]
+#[track_caller]
+pub fn expect_all_none_expr<T>(opt: Option<T>, index: usize, expr: &str)
+where
+    T: fmt::Debug,
+{
+    if let Some(val) = enforced(opt) {
+        expect_all_none_expr_failed(index, expr, &val);
+    }
+}
+
+#[inline(never)]
+#[cold]
+#[track_caller]
+fn expect_all_none_expr_failed(index: usize, expr: &str, value: &dyn fmt::Debug) -> ! {
+    #[cfg(feature = "std")]
+    crate::hook::on_unexpected_some(value);
+    panic_in_context!(
+        "expected argument {} (`{}`) to be None, got Some({:?})",
+        index,
+        expr,
+        value
+    )
+}
+
+#[doc(hidden)]
+#[inline(never)]
+#[cold]
+#[track_caller]
+pub fn assert_none_failed(expr: &str, value: &dyn fmt::Debug, msg: Option<fmt::Arguments>) -> ! {
+    #[cfg(feature = "std")]
+    crate::hook::on_unexpected_some(value);
+    match msg {
+        Some(msg) => panic_in_context!(
+            "assertion `{} is None` failed: {}\nexpected: None\n  actual: Some({:?})",
+            expr,
+            msg,
+            value
+        ),
+        None => panic_in_context!(
+            "assertion `{} is None` failed\nexpected: None\n  actual: Some({:?})",
+            expr,
+            value
+        ),
+    }
+}
+
+#[doc(hidden)]
+#[inline]
+pub fn is_unexpected<T>(opt: Option<T>) -> bool {
+    observed(opt).is_some()
+}
+
+#[doc(hidden)]
+#[inline]
+#[track_caller]
+pub fn assert_none_returns_expr<T>(opt: Option<T>, call: &str)
+where
+    T: fmt::Debug,
+{
+    if let Some(val) = enforced(opt) {
+        assert_none_returns_failed(call, &val);
+    }
+}
+
+#[inline(never)]
+#[cold]
+#[track_caller]
+fn assert_none_returns_failed(call: &str, value: &dyn fmt::Debug) -> ! {
+    #[cfg(feature = "std")]
+    crate::hook::on_unexpected_some(value);
+    panic_in_context!("expected `{}` to return None, got Some({:?})", call, value)
+}
+
+#[doc(hidden)]
+#[inline(never)]
+#[cold]
+#[track_caller]
+pub fn assert_none_matches_failed(expr: &str, pat: &str, value: &dyn fmt::Debug) -> ! {
+    #[cfg(feature = "std")]
+    crate::hook::on_unexpected_some(value);
+    panic_in_context!(
+        "expected `{}` to be None or match `{}`, got Some({:?})",
+        expr,
+        pat,
+        value
+    )
+}
+
+#[doc(hidden)]
+#[inline(never)]
+#[cold]
+#[track_caller]
+pub fn expect_none_field_failed(msg: &str, field: &str, value: &dyn fmt::Debug) -> ! {
+    #[cfg(feature = "std")]
+    crate::hook::on_unexpected_some(value);
+    panic_in_context!("{}: field `{}` is Some({:?})", msg, field, value)
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __claim_assert_none {
+    ($cond:expr $(,)?) => {
+        match $crate::macros::enforced($cond) {
+            ::core::option::Option::None => {}
+            ::core::option::Option::Some(val) => {
+                $crate::macros::claim_assert_none_failed(&val, ::core::option::Option::None)
+            }
+        }
+    };
+    ($cond:expr, $($arg:tt)+) => {
+        match $crate::macros::enforced($cond) {
+            ::core::option::Option::None => {}
+            ::core::option::Option::Some(val) => $crate::macros::claim_assert_none_failed(
+                &val,
+                ::core::option::Option::Some(::core::format_args!($($arg)+)),
+            ),
+        }
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __claim_debug_assert_none {
+    ($($arg:tt)*) => {
+        #[cfg(debug_assertions)]
+        $crate::__claim_assert_none!($($arg)*);
+    };
+}
+
+#[doc(hidden)]
+#[inline(never)]
+#[cold]
+#[track_caller]
+pub fn claim_assert_none_failed(value: &dyn fmt::Debug, msg: Option<fmt::Arguments>) -> ! {
+    #[cfg(feature = "std")]
+    crate::hook::on_unexpected_some(value);
+    match msg {
+        Some(msg) => panic_in_context!(
+            "assertion failed, expected None, got Some({:?}): {}",
+            value,
+            msg
+        ),
+        None => panic_in_context!("assertion failed, expected None, got Some({:?})", value),
+    }
+}
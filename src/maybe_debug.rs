@@ -0,0 +1,36 @@
+/// The bound on the content of the option for
+/// [`UnwrapNone::expect_none`](crate::UnwrapNone::expect_none) and
+/// [`UnwrapNone::unwrap_none`](crate::UnwrapNone::unwrap_none).
+///
+/// By default this is equivalent to [`Debug`](core::fmt::Debug). With the
+/// `minimal-panic` feature it is implemented for every type instead, and the
+/// content is never formatted: the methods panic with only a static message.
+/// This avoids the formatting code for every `T`, which saves space in
+/// firmware with many call sites.
+///
+/// # Examples
+///
+/// ```
+/// use unwrap_none::UnwrapNone;
+///
+/// // `Debug` is only needed without the `minimal-panic` feature.
+/// #[cfg_attr(not(feature = "minimal-panic"), derive(Debug))]
+/// struct Handle(u32);
+///
+/// let slot: Option<Handle> = None;
+/// slot.unwrap_none();
+/// ```
+pub trait MaybeDebug: bound::Bound {}
+
+impl<T> MaybeDebug for T where T: bound::Bound + ?Sized {}
+
+mod bound {
+    #[cfg(not(feature = "minimal-panic"))]
+    pub use core::fmt::Debug as Bound;
+
+    #[cfg(feature = "minimal-panic")]
+    pub trait Bound {}
+
+    #[cfg(feature = "minimal-panic")]
+    impl<T> Bound for T where T: ?Sized {}
+}
@@ -0,0 +1,49 @@
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+static UNEXPECTED_SOME: AtomicUsize = AtomicUsize::new(0);
+
+/// Returns how many times a [`Some`] was observed where [`None`] was expected.
+///
+/// The count is incremented by every method, function and macro of this crate
+/// that observes an unexpected [`Some`], whether it panics or not and
+/// regardless of the `enforce` feature. Checks of other types, such as
+/// [`UnwrapEmpty`](crate::UnwrapEmpty), are not counted. Neither is
+/// [`const_unwrap_none`](crate::const_unwrap_none), which cannot access the
+/// counter in `const` contexts.
+///
+/// Combinators that hand the option back, such as
+/// [`inspect_some`](crate::UnwrapNone::inspect_some),
+/// [`is_none_checked`](crate::UnwrapNone::is_none_checked) and
+/// [`into_unexpected`](crate::UnwrapNone::into_unexpected), do not count it
+/// either, so a chain of them ending in a check counts every [`Some`] once.
+///
+/// This is a lightweight alternative to logging for e.g. long-running
+/// embedded systems that poll for anomalies. It requires a target with atomic
+/// operations on pointer-sized integers.
+///
+/// Available with the `metrics` feature.
+///
+/// # Examples
+///
+/// ```
+/// use unwrap_none::{unexpected_some_count, UnwrapNone};
+///
+/// assert_eq!(unexpected_some_count(), 0);
+///
+/// None::<i32>.unwrap_none_or_else(|_| {});
+/// assert_eq!(unexpected_some_count(), 0);
+///
+/// Some(1).unwrap_none_or_else(|_| {});
+/// assert!(Some(2).try_unwrap_none().is_err());
+/// assert_eq!(unexpected_some_count(), 2);
+///
+/// Some(3).inspect_some(|_| {}).unwrap_none_or_else(|_| {});
+/// assert_eq!(unexpected_some_count(), 3);
+/// ```
+pub fn unexpected_some_count() -> usize {
+    UNEXPECTED_SOME.load(Ordering::Relaxed)
+}
+
+pub(crate) fn record_unexpected_some() {
+    UNEXPECTED_SOME.fetch_add(1, Ordering::Relaxed);
+}
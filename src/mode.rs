@@ -0,0 +1,98 @@
+use core::panic::Location;
+use core::sync::atomic::{AtomicBool, Ordering};
+
+static LOG_ONLY: AtomicBool = AtomicBool::new(false);
+
+/// What the panicking methods of this crate do when they find an unexpected
+/// [`Some`].
+///
+/// Available with the `std` feature.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum PanicMode {
+    /// Panic, as documented by each method. This is the default.
+    #[default]
+    Panic,
+    /// Log an error and return normally, as if the value were [`None`].
+    ///
+    /// The error includes the type name of the value and the caller location.
+    /// It is logged with the `log` feature, with the crate name as target, and
+    /// printed to standard error otherwise.
+    LogOnly,
+}
+
+/// Sets what the panicking methods of this crate do when they find an
+/// unexpected [`Some`], for all threads.
+///
+/// This allows downgrading panics to errors at runtime, e.g. from a
+/// configuration flag while mitigating an incident. It applies to every method,
+/// function and macro expecting an [`Option`] to be [`None`], but not to checks
+/// of other types, such as [`UnwrapEmpty`](crate::UnwrapEmpty), nor to
+/// [`const_unwrap_none`](crate::const_unwrap_none). Checks that panic for
+/// something other than a [`Some`] still do so, i.e. for a repeated call of
+/// [`expect_none_once`](crate::UnwrapNone::expect_none_once), an earlier
+/// caught failure in
+/// [`expect_none_strict`](crate::UnwrapNone::expect_none_strict), and a value
+/// [`expect_blank`](crate::expect_blank) fails to serialize. Since the value is
+/// dropped without being formatted, the hook registered with
+/// [`set_on_unexpected_some`](crate::set_on_unexpected_some) is not called
+/// either.
+///
+/// Available with the `std` feature.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashMap;
+/// use std::panic;
+///
+/// use unwrap_none::{PanicMode, UnwrapNone};
+///
+/// let mut map = HashMap::new();
+/// map.insert(1, "one").unwrap_none();
+///
+/// unwrap_none::set_panic_mode(PanicMode::LogOnly);
+/// assert_eq!(unwrap_none::panic_mode(), PanicMode::LogOnly);
+/// // This logs an error instead of panicking.
+/// map.insert(1, "uno").expect_none("duplicate key");
+///
+/// unwrap_none::set_panic_mode(PanicMode::Panic);
+/// # #[cfg(feature = "enforce")] {
+/// assert!(panic::catch_unwind(move || map.insert(1, "eins").unwrap_none()).is_err());
+/// # }
+/// ```
+pub fn set_panic_mode(mode: PanicMode) {
+    LOG_ONLY.store(mode == PanicMode::LogOnly, Ordering::Relaxed);
+}
+
+/// Returns the mode set with [`set_panic_mode`].
+///
+/// Available with the `std` feature.
+pub fn panic_mode() -> PanicMode {
+    if LOG_ONLY.load(Ordering::Relaxed) {
+        PanicMode::LogOnly
+    } else {
+        PanicMode::Panic
+    }
+}
+
+/// Logs the unexpected value if the mode is [`PanicMode::LogOnly`], returning
+/// whether it did.
+#[inline(never)]
+#[cold]
+#[track_caller]
+pub(crate) fn log_only(type_name: &str) -> bool {
+    if panic_mode() == PanicMode::Panic {
+        return false;
+    }
+    let location = Location::caller();
+    #[cfg(feature = "log")]
+    log::error!(
+        target: env!("CARGO_CRATE_NAME"),
+        "expected None, got Some(<{}>) at {}",
+        type_name,
+        location
+    );
+    #[cfg(not(feature = "log"))]
+    std::eprintln!("expected None, got Some(<{}>) at {}", type_name, location);
+    true
+}
@@ -0,0 +1,49 @@
+use core::fmt;
+
+use crate::failed;
+
+/// Expects an `Option<Option<T>>` to not contain a value.
+///
+/// Nested options often store "maybe present, maybe null" values, where
+/// `Some(None)` still means there is no value.
+pub trait ExpectFlatNone {
+    /// Consumes `self` while expecting [`None`] or `Some(None)` and returning
+    /// nothing.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is a `Some(Some(_))`, with a panic message
+    /// including the passed message, and the content of the inner [`Some`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::panic;
+    ///
+    /// use unwrap_none::ExpectFlatNone;
+    ///
+    /// None::<Option<u32>>.expect_flat_none("nickname is set");
+    /// Some(None::<u32>).expect_flat_none("nickname is set");
+    ///
+    /// # #[cfg(feature = "enforce")] {
+    /// let err = panic::catch_unwind(|| Some(Some("bob")).expect_flat_none("nickname is set"))
+    ///     .unwrap_err();
+    /// let msg = err.downcast_ref::<String>().unwrap();
+    /// assert_eq!(msg, "nickname is set: \"bob\"");
+    /// # }
+    /// ```
+    fn expect_flat_none(self, msg: &str);
+}
+
+impl<T> ExpectFlatNone for Option<Option<T>>
+where
+    T: fmt::Debug,
+{
+    #[inline]
+    #[track_caller]
+    fn expect_flat_none(self, msg: &str) {
+        if let Some(val) = failed::enforced(self.flatten()) {
+            failed::expect_none_failed(&msg, &val);
+        }
+    }
+}
@@ -0,0 +1,14 @@
+use std::collections::BTreeSet;
+use std::panic::Location;
+use std::sync::{Mutex, PoisonError};
+
+static CALLED: Mutex<BTreeSet<Location<'static>>> = Mutex::new(BTreeSet::new());
+
+/// Remembers `location`, returning whether it wasn't seen before.
+#[inline(never)]
+pub(crate) fn first_call(location: &'static Location<'static>) -> bool {
+    CALLED
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .insert(*location)
+}
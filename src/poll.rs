@@ -0,0 +1,67 @@
+use core::fmt;
+use core::task::Poll;
+
+use crate::failed;
+
+/// Expects a [`Poll`] to be [`Pending`](Poll::Pending).
+///
+/// This is useful when manually polling futures in tests, e.g. to check that a
+/// future is not ready on the first poll.
+pub trait ExpectPending {
+    /// Consumes `self` while expecting [`Pending`](Poll::Pending) and
+    /// returning nothing.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is [`Ready`](Poll::Ready), with a panic message
+    /// including the passed message, and the content of the
+    /// [`Ready`](Poll::Ready).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::future::{self, Future};
+    /// use std::pin::pin;
+    /// use std::task::{Context, Waker};
+    ///
+    /// use unwrap_none::ExpectPending;
+    ///
+    /// let mut cx = Context::from_waker(Waker::noop());
+    /// let mut never = pin!(future::pending::<u32>());
+    /// never.as_mut().poll(&mut cx).expect_pending("future resolved early");
+    /// ```
+    ///
+    /// ```
+    /// use std::future::{self, Future};
+    /// use std::panic;
+    /// use std::pin::pin;
+    /// use std::task::{Context, Waker};
+    ///
+    /// use unwrap_none::ExpectPending;
+    ///
+    /// let mut cx = Context::from_waker(Waker::noop());
+    /// let mut ready = pin!(future::ready(5));
+    /// let poll = ready.as_mut().poll(&mut cx);
+    /// # #[cfg(feature = "enforce")] {
+    /// let err = panic::catch_unwind(|| poll.expect_pending("future resolved early")).unwrap_err();
+    /// assert_eq!(err.downcast_ref::<String>().unwrap(), "future resolved early: 5");
+    /// # }
+    /// ```
+    fn expect_pending(self, msg: &str);
+}
+
+impl<T> ExpectPending for Poll<T>
+where
+    T: fmt::Debug,
+{
+    #[inline]
+    #[track_caller]
+    fn expect_pending(self, msg: &str) {
+        if !failed::ENFORCE {
+            return;
+        }
+        if let Poll::Ready(val) = self {
+            failed::expect_none_failed(&msg, &val);
+        }
+    }
+}
@@ -0,0 +1,125 @@
+use core::fmt;
+
+use crate::failed;
+
+/// Expects a `Result<(), E>` to be [`Ok`].
+///
+/// This is the counterpart of [`UnwrapNone`](crate::UnwrapNone) for APIs where
+/// [`Err`] is the "something happened" case.
+pub trait ExpectOkUnit {
+    /// Consumes `self` while expecting [`Ok`] and returning nothing.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is an [`Err`], with a panic message including the
+    /// passed message, and the content of the [`Err`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use unwrap_none::ExpectOkUnit;
+    ///
+    /// let outcome: Result<(), &str> = Ok(());
+    /// outcome.expect_ok("no conflict");
+    /// ```
+    ///
+    /// ```should_panic
+    /// use unwrap_none::ExpectOkUnit;
+    ///
+    /// let outcome: Result<(), &str> = Err("conflict");
+    /// outcome.expect_ok("no conflict");
+    /// # #[cfg(not(feature = "enforce"))]
+    /// # panic!("the checks are disabled");
+    /// ```
+    ///
+    /// ```
+    /// use std::panic;
+    ///
+    /// use unwrap_none::ExpectOkUnit;
+    ///
+    /// # #[cfg(feature = "enforce")] {
+    /// let err = panic::catch_unwind(|| Err::<(), _>(42).expect_ok("no conflict")).unwrap_err();
+    /// assert_eq!(err.downcast_ref::<String>().unwrap(), "no conflict: 42");
+    /// # }
+    /// ```
+    fn expect_ok(self, msg: &str);
+}
+
+impl<E> ExpectOkUnit for Result<(), E>
+where
+    E: fmt::Debug,
+{
+    #[inline]
+    #[track_caller]
+    fn expect_ok(self, msg: &str) {
+        if !failed::ENFORCE {
+            return;
+        }
+        if let Err(err) = self {
+            failed::expect_none_failed(&msg, &err);
+        }
+    }
+}
+
+/// Expects an `Option<Result<T, E>>` to be [`None`].
+///
+/// Unlike [`UnwrapNone::expect_none`](crate::UnwrapNone::expect_none), the
+/// panic message names the variant of the inner [`Result`] and only formats
+/// its content, instead of the nested `Some(Ok(..))`.
+pub trait ExpectNoneInner {
+    /// Consumes `self` while expecting [`None`] and returning nothing.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is a [`Some`], with a panic message including the
+    /// passed message, whether the inner value is an [`Ok`] or an [`Err`], and
+    /// its content.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use unwrap_none::ExpectNoneInner;
+    ///
+    /// let pending: Option<Result<u32, &str>> = None;
+    /// pending.expect_none_inner("request already completed");
+    /// ```
+    ///
+    /// ```
+    /// use std::panic;
+    ///
+    /// use unwrap_none::ExpectNoneInner;
+    ///
+    /// # #[cfg(feature = "enforce")] {
+    /// let err = panic::catch_unwind(|| {
+    ///     Some(Ok::<_, &str>(200)).expect_none_inner("request already completed")
+    /// })
+    /// .unwrap_err();
+    /// let msg = err.downcast_ref::<String>().unwrap();
+    /// assert_eq!(msg, "request already completed: got `Ok`: 200");
+    ///
+    /// let err = panic::catch_unwind(|| {
+    ///     Some(Err::<u32, _>("timeout")).expect_none_inner("request already completed")
+    /// })
+    /// .unwrap_err();
+    /// let msg = err.downcast_ref::<String>().unwrap();
+    /// assert_eq!(msg, "request already completed: got `Err`: \"timeout\"");
+    /// # }
+    /// ```
+    fn expect_none_inner(self, msg: &str);
+}
+
+impl<T, E> ExpectNoneInner for Option<Result<T, E>>
+where
+    T: fmt::Debug,
+    E: fmt::Debug,
+{
+    #[inline]
+    #[track_caller]
+    fn expect_none_inner(self, msg: &str) {
+        match failed::enforced(self) {
+            None => {}
+            Some(Ok(val)) => failed::expect_none_failed_variant(msg, "Ok", &val),
+            Some(Err(err)) => failed::expect_none_failed_variant(msg, "Err", &err),
+        }
+    }
+}
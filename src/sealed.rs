@@ -0,0 +1,8 @@
+/// Prevents downstream crates from implementing [`UnwrapNone`](crate::UnwrapNone).
+///
+/// The module is private, so this trait can be named as a supertrait, but not
+/// implemented outside of this crate. That allows adding methods to the
+/// sealed traits without a breaking change.
+pub trait Sealed {}
+
+impl<T> Sealed for Option<T> {}
@@ -0,0 +1,82 @@
+use crate::failed;
+
+/// Expects an [`Option`] to be [`None`] without formatting its content.
+///
+/// This is implemented for every `Option<T>`, even if `T` does not implement
+/// [`Debug`](core::fmt::Debug), e.g. for foreign types. The panic message only
+/// includes the passed or a static message.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// use unwrap_none::UnwrapNoneSilent;
+///
+/// // Doesn't implement `Debug`.
+/// struct Connection;
+///
+/// let mut connections = HashMap::new();
+/// connections.insert(1, Connection).expect_none_silent("duplicate id");
+/// connections.insert(2, Connection).unwrap_none_silent();
+/// ```
+pub trait UnwrapNoneSilent {
+    /// Consumes `self` while expecting [`None`] and returning nothing.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is a [`Some`], with only the passed message as
+    /// panic message.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::panic;
+    ///
+    /// use unwrap_none::UnwrapNoneSilent;
+    ///
+    /// struct Connection;
+    ///
+    /// # #[cfg(feature = "enforce")] {
+    /// let err = panic::catch_unwind(|| Some(Connection).expect_none_silent("duplicate id"))
+    ///     .unwrap_err();
+    /// assert_eq!(err.downcast_ref::<String>().unwrap(), "duplicate id");
+    /// # }
+    /// ```
+    fn expect_none_silent(self, msg: &str);
+
+    /// Consumes `self` while expecting [`None`] and returning nothing.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is a [`Some`], with a static panic message.
+    ///
+    /// # Examples
+    ///
+    /// ```should_panic
+    /// use unwrap_none::UnwrapNoneSilent;
+    ///
+    /// struct Connection;
+    ///
+    /// Some(Connection).unwrap_none_silent();
+    /// # #[cfg(not(feature = "enforce"))]
+    /// # panic!("the checks are disabled");
+    /// ```
+    fn unwrap_none_silent(self);
+}
+
+impl<T> UnwrapNoneSilent for Option<T> {
+    #[inline]
+    #[track_caller]
+    fn expect_none_silent(self, msg: &str) {
+        if failed::enforced(self).is_some() {
+            failed::none_failed(msg);
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn unwrap_none_silent(self) {
+        self.expect_none_silent("called `Option::unwrap_none_silent()` on a `Some` value");
+    }
+}
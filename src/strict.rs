@@ -0,0 +1,18 @@
+use core::cell::Cell;
+
+std::thread_local! {
+    static FAILED: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Remembers that a check on the current thread is about to panic.
+#[inline]
+pub(crate) fn record_failure() {
+    FAILED.with(|failed| failed.set(true));
+}
+
+/// Returns whether a check on the current thread panicked since the last call,
+/// and forgets about it.
+#[inline]
+pub(crate) fn take_failure() -> bool {
+    FAILED.with(|failed| failed.replace(false))
+}
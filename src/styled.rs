@@ -0,0 +1,31 @@
+/// How [`UnwrapNone::expect_none_styled`](crate::UnwrapNone::expect_none_styled)
+/// formats the unexpected value in its panic message.
+///
+/// The default formats the value like
+/// [`expect_none`](crate::UnwrapNone::expect_none), i.e. with `{:?}`.
+///
+/// # Examples
+///
+/// ```
+/// use unwrap_none::FormatFlags;
+///
+/// let flags = FormatFlags {
+///     alternate: true,
+///     ..FormatFlags::default()
+/// };
+/// assert_eq!(flags.width, None);
+/// assert!(!flags.hide_value);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct FormatFlags {
+    /// Pretty-prints the value with `{:#?}`, like
+    /// [`expect_none_pretty`](crate::UnwrapNone::expect_none_pretty).
+    pub alternate: bool,
+    /// The minimum width passed on to the [`Debug`](core::fmt::Debug)
+    /// implementation of the value, as with `{:width$?}`.
+    pub width: Option<usize>,
+    /// Leaves the value out entirely, like
+    /// [`unwrap_none_redacted`](crate::UnwrapNone::unwrap_none_redacted). The
+    /// other flags are ignored then.
+    pub hide_value: bool,
+}
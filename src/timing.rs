@@ -0,0 +1,60 @@
+use core::cell::Cell;
+
+std::thread_local! {
+    static STATS: Cell<TimedStats> = const { Cell::new(TimedStats { calls: 0, some: 0 }) };
+}
+
+/// How often [`UnwrapNone::expect_none_timed`](crate::UnwrapNone::expect_none_timed)
+/// was called on the current thread, returned by [`timed_stats`].
+///
+/// Available with the `timing` feature.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct TimedStats {
+    /// The amount of calls, including those that panicked.
+    pub calls: u64,
+    /// The amount of calls that found a [`Some`].
+    pub some: u64,
+}
+
+/// Returns how often
+/// [`UnwrapNone::expect_none_timed`](crate::UnwrapNone::expect_none_timed)
+/// was called on the current thread.
+///
+/// This shows how hot the checks are in a real workload. The counters are kept
+/// per thread, so reading them never contends with other threads.
+///
+/// Available with the `timing` feature.
+///
+/// # Examples
+///
+/// ```
+/// use std::panic;
+///
+/// use unwrap_none::{timed_stats, TimedStats, UnwrapNone};
+///
+/// assert_eq!(timed_stats(), TimedStats { calls: 0, some: 0 });
+///
+/// for _ in 0..3 {
+///     None::<u32>.expect_none_timed("slot is in use");
+/// }
+/// # #[cfg(feature = "enforce")] {
+/// assert!(panic::catch_unwind(|| Some(1).expect_none_timed("slot is in use")).is_err());
+/// assert_eq!(timed_stats(), TimedStats { calls: 4, some: 1 });
+///
+/// // Other threads have their own counters.
+/// std::thread::spawn(|| assert_eq!(timed_stats().calls, 0)).join().unwrap();
+/// # }
+/// ```
+pub fn timed_stats() -> TimedStats {
+    STATS.with(Cell::get)
+}
+
+#[inline]
+pub(crate) fn record(some: bool) {
+    STATS.with(|stats| {
+        let mut current = stats.get();
+        current.calls += 1;
+        current.some += u64::from(some);
+        stats.set(current);
+    });
+}
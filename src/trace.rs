@@ -0,0 +1,8 @@
+use core::fmt;
+use core::panic::Location;
+
+#[inline(never)]
+#[cold]
+pub(crate) fn trace_unexpected(value: &dyn fmt::Debug, location: &Location<'_>) {
+    tracing::error!(value = ?value, location = %location, "expected None, got Some");
+}
@@ -0,0 +1,56 @@
+use std::borrow::Cow;
+use std::sync::{PoisonError, RwLock};
+
+type Translator = fn(&str) -> Cow<'_, str>;
+
+static TRANSLATOR: RwLock<Translator> = RwLock::new(identity);
+
+fn identity(msg: &str) -> Cow<'_, str> {
+    Cow::Borrowed(msg)
+}
+
+/// Registers a global function that translates the message passed to
+/// [`expect_none`](crate::UnwrapNone::expect_none) before it is formatted into
+/// the panic message.
+///
+/// This allows using the message as a key, e.g. `expect_none("duplicate_key")`,
+/// which is resolved to a localized string at runtime. It replaces any
+/// previously registered translator. The default returns the message as is.
+///
+/// Available with the `std` feature.
+///
+/// # Examples
+///
+/// ```
+/// use std::borrow::Cow;
+/// use std::panic;
+///
+/// use unwrap_none::UnwrapNone;
+///
+/// fn german(key: &str) -> Cow<'_, str> {
+///     match key {
+///         "duplicate_key" => "doppelter Schlüssel".into(),
+///         _ => key.into(),
+///     }
+/// }
+///
+/// unwrap_none::set_message_translator(german);
+///
+/// # #[cfg(feature = "enforce")] {
+/// let err = panic::catch_unwind(|| Some(3).expect_none("duplicate_key")).unwrap_err();
+/// # #[cfg(not(feature = "minimal-panic"))]
+/// assert_eq!(err.downcast_ref::<String>().unwrap(), "doppelter Schlüssel: 3");
+///
+/// let err = panic::catch_unwind(|| Some(3).expect_none("unknown_key")).unwrap_err();
+/// # #[cfg(not(feature = "minimal-panic"))]
+/// assert_eq!(err.downcast_ref::<String>().unwrap(), "unknown_key: 3");
+/// # }
+/// ```
+pub fn set_message_translator(f: Translator) {
+    *TRANSLATOR.write().unwrap_or_else(PoisonError::into_inner) = f;
+}
+
+pub(crate) fn translate(msg: &str) -> Cow<'_, str> {
+    let translator = *TRANSLATOR.read().unwrap_or_else(PoisonError::into_inner);
+    translator(msg)
+}
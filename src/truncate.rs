@@ -0,0 +1,48 @@
+use core::fmt::{self, Write};
+
+/// Formats a value with [`Debug`](fmt::Debug), cutting it off after
+/// `max_len` bytes.
+pub(crate) struct Truncated<'a> {
+    pub value: &'a dyn fmt::Debug,
+    pub max_len: usize,
+}
+
+impl fmt::Display for Truncated<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut w = LimitedWriter {
+            inner: f,
+            remaining: self.max_len,
+            truncated: false,
+        };
+        let res = write!(w, "{:?}", self.value);
+        if w.truncated {
+            f.write_str("...")
+        } else {
+            res
+        }
+    }
+}
+
+struct LimitedWriter<'a, 'b> {
+    inner: &'a mut fmt::Formatter<'b>,
+    remaining: usize,
+    truncated: bool,
+}
+
+impl Write for LimitedWriter<'_, '_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        if let Some(remaining) = self.remaining.checked_sub(s.len()) {
+            self.remaining = remaining;
+            return self.inner.write_str(s);
+        }
+        let mut end = self.remaining;
+        while !s.is_char_boundary(end) {
+            end -= 1;
+        }
+        self.inner.write_str(&s[..end])?;
+        self.remaining = 0;
+        self.truncated = true;
+        // Stop formatting the rest of the value, there is no room for it.
+        Err(fmt::Error)
+    }
+}
@@ -0,0 +1,69 @@
+use core::str;
+
+use ufmt::{uDebug, uWrite};
+
+use crate::failed::panic_in_context;
+
+const CAPACITY: usize = 128;
+
+/// A fixed-size buffer the value is formatted into, so `ufmt` can be used
+/// without `alloc`. Output that doesn't fit is cut off at a char boundary.
+struct Buffer {
+    bytes: [u8; CAPACITY],
+    len: usize,
+    truncated: bool,
+}
+
+impl uWrite for Buffer {
+    type Error = ();
+
+    fn write_str(&mut self, s: &str) -> Result<(), ()> {
+        let space = CAPACITY - self.len;
+        let mut n = s.len().min(space);
+        while !s.is_char_boundary(n) {
+            n -= 1;
+        }
+        self.bytes[self.len..self.len + n].copy_from_slice(&s.as_bytes()[..n]);
+        self.len += n;
+        if n < s.len() {
+            self.truncated = true;
+            // Stop formatting, there's no space left anyway.
+            return Err(());
+        }
+        Ok(())
+    }
+}
+
+// The formatting itself has to be generic since uDebug isn't object safe, but
+// panicking is not.
+#[inline(never)]
+#[cold]
+#[track_caller]
+pub(crate) fn unwrap_none_failed_ufmt<T>(value: &T) -> !
+where
+    T: uDebug + ?Sized,
+{
+    let mut buf = Buffer {
+        bytes: [0; CAPACITY],
+        len: 0,
+        truncated: false,
+    };
+    let _ = ufmt::uwrite!(buf, "{:?}", value);
+    buffer_failed(&buf)
+}
+
+#[inline(never)]
+#[cold]
+#[track_caller]
+fn buffer_failed(buf: &Buffer) -> ! {
+    const MSG: &str = "called `Option::unwrap_none_ufmt()` on a `Some` value";
+    // The buffer only ever contains complete chars.
+    let value = str::from_utf8(&buf.bytes[..buf.len]).unwrap_or_default();
+    #[cfg(feature = "std")]
+    crate::hook::on_unexpected_some(&crate::failed::DisplayAsDebug(&value));
+    if buf.truncated {
+        panic_in_context!("{}: {}...", MSG, value)
+    } else {
+        panic_in_context!("{}: {}", MSG, value)
+    }
+}
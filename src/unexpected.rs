@@ -0,0 +1,74 @@
+use core::fmt;
+use core::panic::Location;
+
+/// The panic payload of
+/// [`UnwrapNone::unwrap_none_typed_panic`](crate::UnwrapNone::unwrap_none_typed_panic),
+/// wrapping the unexpected content of a [`Some`] and where it was found.
+///
+/// Downcast the payload returned by [`std::panic::catch_unwind`] to this type
+/// to recover the value. To continue unwinding instead, pass the payload to
+/// [`std::panic::resume_unwind`].
+///
+/// Available with the `std` feature.
+///
+/// # Examples
+///
+/// ```
+/// use std::panic;
+///
+/// use unwrap_none::{UnexpectedSome, UnwrapNone};
+///
+/// # #[cfg(feature = "enforce")] {
+/// let payload = panic::catch_unwind(|| Some(5).unwrap_none_typed_panic()).unwrap_err();
+/// let mut unexpected = payload.downcast::<UnexpectedSome<i32>>().unwrap();
+/// assert_eq!(*unexpected.value(), 5);
+/// assert_eq!(unexpected.location().file(), file!());
+///
+/// *unexpected.value_mut() += 1;
+/// assert_eq!(unexpected.into_inner(), 6);
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnexpectedSome<T> {
+    value: T,
+    location: &'static Location<'static>,
+}
+
+impl<T> UnexpectedSome<T> {
+    pub(crate) fn new(value: T, location: &'static Location<'static>) -> Self {
+        Self { value, location }
+    }
+
+    /// Returns a reference to the unexpected value.
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+
+    /// Returns a mutable reference to the unexpected value.
+    pub fn value_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+
+    /// Returns where the unexpected value was found.
+    pub fn location(&self) -> &'static Location<'static> {
+        self.location
+    }
+
+    /// Consumes the payload, returning the unexpected value.
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T> fmt::Display for UnexpectedSome<T>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "expected None, got Some({:?}) at {}",
+            self.value, self.location
+        )
+    }
+}
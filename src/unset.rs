@@ -0,0 +1,48 @@
+use crate::failed;
+
+/// Expects an `Option<()>` flag to be unset.
+///
+/// `Option<()>` is a common way to signal that a flag was already set, e.g.
+/// by [`HashMap::insert`] on a `HashMap<K, ()>`. The content is always `()`,
+/// so unlike [`UnwrapNone::expect_none`](crate::UnwrapNone::expect_none) it is
+/// left out of the panic message.
+///
+/// [`HashMap::insert`]: https://doc.rust-lang.org/std/collections/struct.HashMap.html#method.insert
+pub trait ExpectUnset {
+    /// Consumes `self` while expecting [`None`] and returning nothing.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is a [`Some`], with the passed message as panic
+    /// message.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use std::panic;
+    ///
+    /// use unwrap_none::ExpectUnset;
+    ///
+    /// let mut seen = HashMap::new();
+    /// seen.insert("a", ()).expect_unset("already seen");
+    /// seen.insert("b", ()).expect_unset("already seen");
+    ///
+    /// # #[cfg(feature = "enforce")] {
+    /// let err = panic::catch_unwind(move || seen.insert("a", ()).expect_unset("already seen"))
+    ///     .unwrap_err();
+    /// assert_eq!(err.downcast_ref::<String>().unwrap(), "already seen");
+    /// # }
+    /// ```
+    fn expect_unset(self, msg: &str);
+}
+
+impl ExpectUnset for Option<()> {
+    #[inline]
+    #[track_caller]
+    fn expect_unset(self, msg: &str) {
+        if failed::enforced(self).is_some() {
+            failed::none_failed(msg);
+        }
+    }
+}
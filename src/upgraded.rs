@@ -0,0 +1,81 @@
+use alloc::rc::Rc;
+#[cfg(target_has_atomic = "ptr")]
+use alloc::sync::Arc;
+
+use crate::failed;
+
+/// Expects the result of upgrading a weak pointer to be [`None`], i.e. the
+/// referent to be gone.
+///
+/// Available with the `alloc` feature.
+pub trait ExpectUpgradedNone {
+    /// Consumes `self` while expecting [`None`] and returning nothing.
+    ///
+    /// This is meant for the result of [`Weak::upgrade`](alloc::rc::Weak::upgrade),
+    /// e.g. in teardown tests. Instead of formatting the referent, the panic
+    /// message reports how many strong references keep it alive, which is
+    /// usually more helpful in finding the leak.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is a [`Some`], with a panic message including the
+    /// passed message, and the strong count of the referent. The reference
+    /// created by the upgrade itself is not counted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::panic;
+    /// use std::rc::Rc;
+    /// use std::sync::Arc;
+    ///
+    /// use unwrap_none::ExpectUpgradedNone;
+    ///
+    /// let session = Rc::new("session");
+    /// let weak = Rc::downgrade(&session);
+    /// drop(session);
+    /// weak.upgrade().expect_upgraded_none("session leaked");
+    ///
+    /// let session = Arc::new("session");
+    /// let weak = Arc::downgrade(&session);
+    /// let cache = Arc::clone(&session);
+    /// drop(session);
+    /// # #[cfg(feature = "enforce")] {
+    /// let err = panic::catch_unwind(|| weak.upgrade().expect_upgraded_none("session leaked"))
+    ///     .unwrap_err();
+    /// assert_eq!(
+    ///     err.downcast_ref::<String>().unwrap(),
+    ///     "session leaked: still alive with a strong count of 1",
+    /// );
+    /// # drop(cache);
+    /// # }
+    /// ```
+    fn expect_upgraded_none(self, msg: &str);
+}
+
+impl<T> ExpectUpgradedNone for Option<Rc<T>>
+where
+    T: ?Sized,
+{
+    #[inline]
+    #[track_caller]
+    fn expect_upgraded_none(self, msg: &str) {
+        if let Some(rc) = failed::enforced(self) {
+            failed::expect_upgraded_none_failed(msg, Rc::strong_count(&rc) - 1);
+        }
+    }
+}
+
+#[cfg(target_has_atomic = "ptr")]
+impl<T> ExpectUpgradedNone for Option<Arc<T>>
+where
+    T: ?Sized,
+{
+    #[inline]
+    #[track_caller]
+    fn expect_upgraded_none(self, msg: &str) {
+        if let Some(arc) = failed::enforced(self) {
+            failed::expect_upgraded_none_failed(msg, Arc::strong_count(&arc) - 1);
+        }
+    }
+}
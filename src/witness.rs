@@ -0,0 +1,43 @@
+/// A witness that an [`Option`] was checked to be [`None`], returned by
+/// [`UnwrapNone::narrow_none`](crate::UnwrapNone::narrow_none).
+///
+/// It can only be obtained by performing the check, so functions can require
+/// it as a parameter to prove their precondition was established. It is
+/// zero-sized and costs nothing to pass around.
+///
+/// The witness does not remember which option was checked, and with the
+/// `enforce` feature disabled it is returned without checking anything.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// use unwrap_none::{IsNone, UnwrapNone};
+///
+/// struct Registry {
+///     handlers: HashMap<&'static str, fn()>,
+/// }
+///
+/// impl Registry {
+///     // Only callable once the previous handler is known to be absent.
+///     fn announce(&self, name: &str, _: IsNone) {
+///         println!("registered {} ({} total)", name, self.handlers.len());
+///     }
+/// }
+///
+/// let mut registry = Registry { handlers: HashMap::new() };
+/// let proof = registry.handlers.insert("ping", || {}).narrow_none("duplicate handler");
+/// registry.announce("ping", proof);
+/// assert_eq!(std::mem::size_of::<IsNone>(), 0);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct IsNone {
+    _private: (),
+}
+
+impl IsNone {
+    pub(crate) const fn new() -> Self {
+        Self { _private: () }
+    }
+}
@@ -0,0 +1,7 @@
+// The checks compile to nothing without `enforce`, so the cases would build.
+#[cfg(feature = "enforce")]
+#[test]
+fn ui() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+}
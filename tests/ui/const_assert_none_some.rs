@@ -0,0 +1,6 @@
+use unwrap_none::const_assert_none;
+
+const OVERRIDE: Option<u8> = Some(1);
+const_assert_none!(OVERRIDE);
+
+fn main() {}
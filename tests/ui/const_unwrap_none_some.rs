@@ -0,0 +1,6 @@
+use unwrap_none::const_unwrap_none;
+
+const OVERRIDE: Option<u8> = Some(1);
+const _: () = const_unwrap_none(OVERRIDE);
+
+fn main() {}
@@ -0,0 +1,133 @@
+//! Derive macros for the [`unwrap_none`](https://docs.rs/unwrap_none) crate.
+//!
+//! Use these through the `derive` feature of `unwrap_none` instead of
+//! depending on this crate directly.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+    parse_macro_input, parse_quote, Data, DeriveInput, Error, GenericArgument, Index, Path,
+    PathArguments, Type,
+};
+
+/// Implements `unwrap_none::ExpectNoneFields`, checking every field of type
+/// `Option` in declaration order. Other fields are skipped.
+///
+/// Fields are recognized as options by name only, so a field whose type is
+/// spelled e.g. `Option<T>` or `core::option::Option<T>` is checked, but one
+/// using a type alias is not. The content of every checked field must
+/// implement `Debug`, which is added to the `where` clause of the impl.
+///
+/// If `unwrap_none` is not available under that name, e.g. because it is
+/// re-exported by another crate, its path can be passed with
+/// `#[unwrap_none(crate = path::to::unwrap_none)]` on the struct.
+#[proc_macro_derive(ExpectNoneFields, attributes(unwrap_none))]
+pub fn derive_expect_none_fields(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let krate = crate_path(&input)?;
+    let fields = match &input.data {
+        Data::Struct(data) => &data.fields,
+        Data::Enum(data) => {
+            return Err(Error::new(
+                data.enum_token.span,
+                "`ExpectNoneFields` can only be derived for structs",
+            ))
+        }
+        Data::Union(data) => {
+            return Err(Error::new(
+                data.union_token.span,
+                "`ExpectNoneFields` can only be derived for structs",
+            ))
+        }
+    };
+
+    let options = fields
+        .iter()
+        .enumerate()
+        .filter_map(|(i, field)| Some((i, field, option_content(&field.ty)?)));
+
+    let mut generics = input.generics.clone();
+    let where_clause = generics.make_where_clause();
+    for (_, _, content) in options.clone() {
+        where_clause
+            .predicates
+            .push(parse_quote!(#content: ::core::fmt::Debug));
+    }
+
+    let checks = options.map(|(i, field, _)| {
+        let (member, name) = match &field.ident {
+            Some(ident) => (quote!(#ident), ident.to_string()),
+            None => {
+                let index = Index::from(i);
+                (quote!(#index), i.to_string())
+            }
+        };
+        quote! {
+            if let ::core::option::Option::Some(val) = #krate::macros::enforced(
+                ::core::option::Option::as_ref(&self.#member),
+            ) {
+                #krate::macros::expect_none_field_failed(msg, #name, val);
+            }
+        }
+    });
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics #krate::ExpectNoneFields for #name #ty_generics #where_clause {
+            #[track_caller]
+            fn expect_all_none(self, msg: &str) {
+                #(#checks)*
+            }
+        }
+    })
+}
+
+/// Returns the path passed with `#[unwrap_none(crate = ..)]`, or
+/// `::unwrap_none`.
+fn crate_path(input: &DeriveInput) -> syn::Result<Path> {
+    let mut krate = parse_quote!(::unwrap_none);
+    for attr in &input.attrs {
+        if !attr.path().is_ident("unwrap_none") {
+            continue;
+        }
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("crate") {
+                krate = meta.value()?.parse()?;
+                Ok(())
+            } else {
+                Err(meta.error("unknown `unwrap_none` attribute, expected `crate`"))
+            }
+        })?;
+    }
+    Ok(krate)
+}
+
+/// Returns `T` if `ty` is spelled `Option<T>`.
+fn option_content(ty: &Type) -> Option<&Type> {
+    match ty {
+        Type::Path(path) if path.qself.is_none() => {
+            let segment = path.path.segments.last()?;
+            if segment.ident != "Option" {
+                return None;
+            }
+            match &segment.arguments {
+                PathArguments::AngleBracketed(args) => match args.args.first()? {
+                    GenericArgument::Type(content) => Some(content),
+                    _ => None,
+                },
+                _ => None,
+            }
+        }
+        Type::Group(group) => option_content(&group.elem),
+        Type::Paren(paren) => option_content(&paren.elem),
+        _ => None,
+    }
+}